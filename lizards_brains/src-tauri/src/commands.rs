@@ -1,27 +1,116 @@
+use crate::mcp::session::{SessionInfo, SessionManager};
+use crate::settings::Settings;
 use crate::state::StateManager;
-use crate::timer::{Origin, Session, TimerEngine, TimerStatus};
+use crate::timer::{parse_duration_secs, CycleConfig, Origin, ScheduledSession, Session, TimerEngine, TimerStatus};
+use crate::worker::{WorkerInfo, WorkerManager};
 use std::sync::Arc;
 use tauri::State;
+use uuid::Uuid;
 
 #[tauri::command]
 pub async fn start_timer(
     engine: State<'_, TimerEngine>,
-    duration_minutes: u32,
+    settings: State<'_, Settings>,
+    duration_minutes: Option<u32>,
+    duration: Option<String>,
     label: String,
 ) -> Result<String, String> {
-    let session = engine
-        .start(duration_minutes, &label, Origin::Human)
-        .await
-        .map_err(|e| e.to_string())?;
+    let session = match duration {
+        Some(duration) => {
+            let duration_secs = parse_duration_secs(&duration).map_err(|e| e.to_string())?;
+            engine
+                .start_for_secs(duration_secs, &label, Origin::Human)
+                .await
+        }
+        None => {
+            let duration_minutes = duration_minutes.unwrap_or(settings.timer.default_work_minutes);
+            engine.start(duration_minutes, &label, Origin::Human).await
+        }
+    }
+    .map_err(|e| e.to_string())?;
     serde_json::to_string(&session).map_err(|e| e.to_string())
 }
 
+/// Persists a session to start at a future time, optionally repeating on a
+/// fixed interval. Picked up by the background scheduler worker once due.
+#[tauri::command]
+pub async fn schedule_timer(
+    state: State<'_, Arc<StateManager>>,
+    label: String,
+    duration: String,
+    start_at: String,
+    recurrence: Option<String>,
+) -> Result<String, String> {
+    let label = TimerEngine::validate_label(&label).map_err(|e| e.to_string())?;
+    let duration_secs = parse_duration_secs(&duration).map_err(|e| e.to_string())?;
+    let duration_secs = TimerEngine::validate_duration_secs(duration_secs).map_err(|e| e.to_string())?;
+    let recurrence_secs = recurrence
+        .as_deref()
+        .map(parse_duration_secs)
+        .transpose()
+        .map_err(|e| e.to_string())?;
+    let scheduled = ScheduledSession {
+        id: Uuid::new_v4().to_string(),
+        label,
+        duration_secs,
+        origin: Origin::Human,
+        run_at: start_at,
+        recurrence_secs,
+    };
+    state
+        .save_scheduled_session(&scheduled)
+        .map_err(|e| e.to_string())?;
+    serde_json::to_string(&scheduled).map_err(|e| e.to_string())
+}
+
 #[tauri::command]
 pub async fn stop_timer(engine: State<'_, TimerEngine>) -> Result<String, String> {
     let session = engine.stop().await.map_err(|e| e.to_string())?;
     serde_json::to_string(&session).map_err(|e| e.to_string())
 }
 
+#[tauri::command]
+pub async fn pause_timer(engine: State<'_, TimerEngine>) -> Result<(), String> {
+    engine.pause().await.map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+pub async fn resume_timer(engine: State<'_, TimerEngine>) -> Result<(), String> {
+    engine.resume().await.map_err(|e| e.to_string())
+}
+
+/// Starts an automatic work/break cycle, resuming the set's position from
+/// the last persisted session if one exists.
+#[tauri::command]
+pub async fn start_cycle(
+    engine: State<'_, TimerEngine>,
+    state: State<'_, Arc<StateManager>>,
+    settings: State<'_, Settings>,
+    label: String,
+    work_minutes: Option<u32>,
+    short_break_minutes: Option<u32>,
+    long_break_minutes: Option<u32>,
+    sessions_before_long_break: Option<u32>,
+) -> Result<String, String> {
+    let resume_cycle_index = state
+        .get_last_session()
+        .map_err(|e| e.to_string())?
+        .map(|s| s.cycle_index)
+        .unwrap_or(0);
+    let config = CycleConfig {
+        work_minutes: work_minutes.unwrap_or(settings.timer.default_work_minutes),
+        short_break_minutes: short_break_minutes.unwrap_or(settings.timer.default_break_minutes),
+        long_break_minutes: long_break_minutes.unwrap_or(settings.timer.default_long_break_minutes),
+        sessions_before_long_break: sessions_before_long_break
+            .unwrap_or(settings.timer.default_sessions_before_long_break),
+    };
+    let session = engine
+        .start_cycle(config, &label, Origin::Human, resume_cycle_index)
+        .await
+        .map_err(|e| e.to_string())?;
+    serde_json::to_string(&session).map_err(|e| e.to_string())
+}
+
 #[tauri::command]
 pub async fn get_status(engine: State<'_, TimerEngine>) -> Result<TimerStatus, String> {
     Ok(engine.get_status().await)
@@ -37,3 +126,14 @@ pub fn get_history(
         .get_history(start_date.as_deref(), end_date.as_deref())
         .map_err(|e| e.to_string())
 }
+
+#[tauri::command]
+pub async fn list_workers(manager: State<'_, WorkerManager>) -> Result<Vec<WorkerInfo>, String> {
+    Ok(manager.list().await)
+}
+
+/// Lists clients currently connected over the optional TCP transport.
+#[tauri::command]
+pub async fn list_sessions(manager: State<'_, SessionManager>) -> Result<Vec<SessionInfo>, String> {
+    Ok(manager.list().await)
+}
@@ -1,8 +1,13 @@
 mod commands;
 pub mod mcp;
+pub mod settings;
 pub mod state;
 pub mod timer;
+pub mod worker;
 
+use async_trait::async_trait;
+use mcp::auth::SocketAuth;
+use mcp::session::SessionManager;
 use state::StateManager;
 use std::sync::Arc;
 use tauri::menu::{Menu, MenuItem};
@@ -11,11 +16,290 @@ use tauri::Emitter;
 use tauri::Manager;
 use tauri_plugin_notification::NotificationExt;
 use timer::{SessionStatus, TimerEngine, TimerEvent};
+use worker::{Worker, WorkerManager};
+
+/// Drives the Unix socket accept loop one connection at a time so the
+/// `WorkerManager` can track its liveness.
+struct SocketListenerWorker {
+    listener: tokio::net::UnixListener,
+    engine: TimerEngine,
+    state: Arc<StateManager>,
+    workers: WorkerManager,
+    auth: Option<Arc<SocketAuth>>,
+    settings: settings::Settings,
+}
+
+#[async_trait]
+impl Worker for SocketListenerWorker {
+    fn id(&self) -> &str {
+        "socket-listener"
+    }
+
+    async fn step(&mut self) -> Result<(), String> {
+        mcp::transport::accept_one(
+            &self.listener,
+            &self.engine,
+            &self.state,
+            &self.workers,
+            self.auth.as_ref(),
+            &self.settings,
+        )
+        .await
+        .map_err(|e| e.to_string())
+    }
+}
+
+/// Forwards timer events from the broadcast channel to Tauri events and the
+/// database one event at a time, so the `WorkerManager` can tell when the
+/// channel closes for good.
+struct EventForwarderWorker {
+    rx: tokio::sync::broadcast::Receiver<TimerEvent>,
+    db: Arc<StateManager>,
+    handle: tauri::AppHandle,
+}
+
+#[async_trait]
+impl Worker for EventForwarderWorker {
+    fn id(&self) -> &str {
+        "event-forwarder"
+    }
+
+    async fn step(&mut self) -> Result<(), String> {
+        match self.rx.recv().await {
+            Ok(event) => {
+                forward_event(&self.db, &self.handle, event);
+                Ok(())
+            }
+            Err(tokio::sync::broadcast::error::RecvError::Lagged(n)) => {
+                eprintln!("Event forwarder lagged by {} events", n);
+                Ok(())
+            }
+            Err(tokio::sync::broadcast::error::RecvError::Closed) => {
+                Err("broadcast channel closed".to_string())
+            }
+        }
+    }
+}
+
+/// Wakes periodically, starts any scheduled sessions whose `run_at` has
+/// passed, and reschedules (or deletes) them afterward.
+struct SchedulerWorker {
+    engine: TimerEngine,
+    state: Arc<StateManager>,
+}
+
+impl SchedulerWorker {
+    /// Advances `run_at` by whole `interval_secs` steps until it's back in
+    /// the future relative to `now`, so a recurrence stays pinned to its
+    /// original wall-clock slot (e.g. "every weekday at 9am") instead of
+    /// drifting to whenever a missed wake-up happened to fire.
+    fn next_occurrence_after(
+        run_at: &str,
+        interval_secs: u64,
+        now: chrono::DateTime<chrono::Utc>,
+    ) -> chrono::DateTime<chrono::Utc> {
+        let interval = chrono::Duration::seconds(interval_secs.max(1) as i64);
+        match chrono::DateTime::parse_from_rfc3339(run_at) {
+            Ok(scheduled_at) => {
+                let mut next = scheduled_at.with_timezone(&chrono::Utc);
+                while next <= now {
+                    next += interval;
+                }
+                next
+            }
+            Err(_) => now + interval,
+        }
+    }
+}
+
+#[async_trait]
+impl Worker for SchedulerWorker {
+    fn id(&self) -> &str {
+        "scheduler"
+    }
+
+    async fn step(&mut self) -> Result<(), String> {
+        tokio::time::sleep(std::time::Duration::from_secs(1)).await;
+
+        let now = chrono::Utc::now();
+        let due = self
+            .state
+            .due_scheduled_sessions(&now.to_rfc3339())
+            .map_err(|e| e.to_string())?;
+
+        for sched in due {
+            match self
+                .engine
+                .start_for_secs(sched.duration_secs, &sched.label, sched.origin.clone())
+                .await
+            {
+                Ok(_) => {}
+                // Another session is already running; leave it due so the
+                // next wake-up retries once the timer frees up.
+                Err(timer::TimerError::AlreadyRunning) => continue,
+                Err(e) => {
+                    eprintln!("Scheduled session {} failed to start: {}", sched.id, e);
+                }
+            }
+
+            match sched.recurrence_secs {
+                Some(interval_secs) => {
+                    let next_run_at = Self::next_occurrence_after(&sched.run_at, interval_secs, now);
+                    if let Err(e) = self
+                        .state
+                        .reschedule_scheduled_session(&sched.id, &next_run_at.to_rfc3339())
+                    {
+                        eprintln!("Failed to reschedule session {}: {}", sched.id, e);
+                    }
+                }
+                None => {
+                    if let Err(e) = self.state.delete_scheduled_session(&sched.id) {
+                        eprintln!("Failed to delete scheduled session {}: {}", sched.id, e);
+                    }
+                }
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Watches the timer engine's event stream so a stalled or panicked tick
+/// loop surfaces as a stale `last_active` timestamp on `list_workers`
+/// instead of silently going quiet.
+struct TimerTickWorker {
+    rx: tokio::sync::broadcast::Receiver<TimerEvent>,
+}
+
+#[async_trait]
+impl Worker for TimerTickWorker {
+    fn id(&self) -> &str {
+        "timer-engine"
+    }
+
+    async fn step(&mut self) -> Result<(), String> {
+        match self.rx.recv().await {
+            Ok(_) => Ok(()),
+            Err(tokio::sync::broadcast::error::RecvError::Lagged(_)) => Ok(()),
+            Err(tokio::sync::broadcast::error::RecvError::Closed) => {
+                Err("broadcast channel closed".to_string())
+            }
+        }
+    }
+}
+
+fn forward_event(db: &Arc<StateManager>, handle: &tauri::AppHandle, event: TimerEvent) {
+    let (event_name, payload) = match &event {
+        TimerEvent::Started { session } => {
+            if let Err(e) = db.save_session(session) {
+                eprintln!("Failed to save session: {}", e);
+            }
+            ("timer:started", serde_json::to_string(session).unwrap())
+        }
+        TimerEvent::Tick {
+            remaining_secs,
+            session,
+        } => {
+            // Update tray tooltip with remaining time
+            let mins = remaining_secs / 60;
+            let secs = remaining_secs % 60;
+            if let Some(tray) = handle.tray_by_id("main") {
+                let _ = tray.set_tooltip(Some(&format!(
+                    "{} - {:02}:{:02}",
+                    session.label, mins, secs
+                )));
+            }
+            (
+                "timer:tick",
+                serde_json::json!({
+                    "remaining_secs": remaining_secs,
+                    "session": session
+                })
+                .to_string(),
+            )
+        }
+        TimerEvent::Completed { session } => {
+            if let Some(ended) = &session.ended_at {
+                if let Err(e) =
+                    db.update_session(&session.id, &SessionStatus::Completed, ended)
+                {
+                    eprintln!("Failed to update session: {}", e);
+                }
+            }
+            // Send system notification
+            if let Ok(true) = handle
+                .notification()
+                .permission_state()
+                .map(|p| p == tauri_plugin_notification::PermissionState::Granted)
+            {
+                let _ = handle
+                    .notification()
+                    .builder()
+                    .title("Timer Complete!")
+                    .body(format!("\"{}\" session finished", session.label))
+                    .show();
+            }
+            // Reset tray tooltip
+            if let Some(tray) = handle.tray_by_id("main") {
+                let _ = tray.set_tooltip(Some("PomodoroAI"));
+            }
+            ("timer:completed", serde_json::to_string(session).unwrap())
+        }
+        TimerEvent::Stopped { session } => {
+            if let Some(ended) = &session.ended_at {
+                if let Err(e) = db.update_session(&session.id, &SessionStatus::Stopped, ended) {
+                    eprintln!("Failed to update session: {}", e);
+                }
+            }
+            // Reset tray tooltip
+            if let Some(tray) = handle.tray_by_id("main") {
+                let _ = tray.set_tooltip(Some("PomodoroAI"));
+            }
+            ("timer:stopped", serde_json::to_string(session).unwrap())
+        }
+    };
+    let _ = handle.emit(event_name, payload);
+}
+
+/// CLI shim entry point. `remote`, when set to a `host:port`, dials the QUIC
+/// transport instead of the local Unix socket, so Claude Code running on
+/// another machine can drive the same `PomodoroMcpService`. A remote
+/// connection has no local copy of the server's auth secret, so `remote`
+/// callers must pass `remote_token_path` pointing at a copy of the server's
+/// token file (see `mcp::auth::token_path`) when `mcp.require_auth` is set.
+pub fn run_mcp_shim(remote: Option<String>, remote_token_path: Option<String>) {
+    let config_dir = dirs::config_dir()
+        .expect("failed to resolve config directory")
+        .join("com.pomodoroai.app");
+    let settings = settings::Settings::load(&config_dir).expect("failed to load settings");
 
-pub fn run_mcp_shim() {
     let rt = tokio::runtime::Runtime::new().expect("failed to create tokio runtime");
     rt.block_on(async {
-        if let Err(e) = mcp::transport::run_mcp_shim().await {
+        let result = match remote {
+            Some(addr) => {
+                let auth = if settings.mcp.require_auth {
+                    let Some(token_path) = remote_token_path.as_deref() else {
+                        eprintln!(
+                            "mcp.require_auth is set; pass --remote-token=<path to a copy of the server's token file>"
+                        );
+                        std::process::exit(1);
+                    };
+                    Some(
+                        SocketAuth::from_file(std::path::Path::new(token_path))
+                            .await
+                            .unwrap_or_else(|e| {
+                                eprintln!("{}", e);
+                                std::process::exit(1);
+                            }),
+                    )
+                } else {
+                    None
+                };
+                mcp::quic_transport::run_shim(&addr, auth.as_ref()).await
+            }
+            None => mcp::transport::run_mcp_shim(&settings).await,
+        };
+        if let Err(e) = result {
             eprintln!("{}", e);
             std::process::exit(1);
         }
@@ -24,16 +308,30 @@ pub fn run_mcp_shim() {
 
 pub fn run_gui() {
     let engine = TimerEngine::new();
-    let mut rx = engine.subscribe();
+    let rx = engine.subscribe();
+    let tick_rx = engine.subscribe();
     let socket_engine = engine.clone();
+    let tcp_engine = engine.clone();
+    let quic_engine = engine.clone();
+    let scheduler_engine = engine.clone();
+    let worker_manager = WorkerManager::new();
+    let session_manager = SessionManager::new();
     tauri::Builder::default()
         .plugin(tauri_plugin_notification::init())
         .manage(engine)
+        .manage(worker_manager)
+        .manage(session_manager)
         .invoke_handler(tauri::generate_handler![
             commands::start_timer,
+            commands::schedule_timer,
             commands::stop_timer,
+            commands::pause_timer,
+            commands::resume_timer,
+            commands::start_cycle,
             commands::get_status,
             commands::get_history,
+            commands::list_workers,
+            commands::list_sessions,
         ])
         .setup(move |app| {
             // System tray
@@ -77,15 +375,32 @@ pub fn run_gui() {
                 }
             });
 
+            // Load layered settings: defaults, overlaid with config.toml, overlaid with env vars.
+            let app_config_dir = app
+                .path()
+                .app_config_dir()
+                .expect("failed to get app config dir");
+            let settings =
+                settings::Settings::load(&app_config_dir).expect("failed to load settings");
+            app.manage(settings.clone());
+
             // Initialize SQLite database
             let app_data_dir = app
                 .path()
                 .app_data_dir()
                 .expect("failed to get app data dir");
             std::fs::create_dir_all(&app_data_dir).expect("failed to create app data dir");
-            let db_path = app_data_dir.join("pomodoro.db");
-            let state_manager = StateManager::new(db_path.to_str().expect("invalid db path"))
-                .expect("failed to initialize database");
+            let state_manager = if settings.database.in_memory {
+                StateManager::in_memory().expect("failed to initialize database")
+            } else {
+                let db_path = settings.database_path(&app_data_dir);
+                StateManager::with_pool_size(
+                    db_path.to_str().expect("invalid db path"),
+                    settings.database.min_connections,
+                    settings.database.max_connections,
+                )
+                .expect("failed to initialize database")
+            };
 
             // Clean up stale running sessions from previous crash
             if let Ok(count) = state_manager.cleanup_stale_running() {
@@ -97,108 +412,115 @@ pub fn run_gui() {
             let state_manager = Arc::new(state_manager);
             app.manage(Arc::clone(&state_manager));
 
-            let db = Arc::clone(&state_manager);
             let handle = app.handle().clone();
+            let worker_manager = app.state::<WorkerManager>().inner().clone();
 
-            // Unix socket listener for MCP clients
+            // Unix socket listener for MCP clients, supervised so a fatal
+            // accept error surfaces as a `Dead` worker instead of a silent eprintln.
             let socket_state = Arc::clone(&state_manager);
+            let socket_path = mcp::transport::socket_path(&settings);
+            let socket_manager = worker_manager.clone();
+            let socket_manager_for_service = socket_manager.clone();
+            let require_auth = settings.mcp.require_auth;
+            let socket_settings = settings.clone();
             tauri::async_runtime::spawn(async move {
-                if let Err(e) =
-                    mcp::transport::start_socket_listener(socket_engine, socket_state).await
-                {
-                    eprintln!("MCP socket listener error: {}", e);
+                let auth = if require_auth {
+                    match SocketAuth::load_or_create(&mcp::auth::token_path(&socket_path)).await {
+                        Ok(auth) => Some(Arc::new(auth)),
+                        Err(e) => {
+                            eprintln!("Failed to load MCP auth token: {}", e);
+                            return;
+                        }
+                    }
+                } else {
+                    None
+                };
+
+                match mcp::transport::bind_socket_listener(&socket_path).await {
+                    Ok(listener) => {
+                        socket_manager.spawn(SocketListenerWorker {
+                            listener,
+                            engine: socket_engine,
+                            state: socket_state,
+                            workers: socket_manager_for_service,
+                            auth,
+                            settings: socket_settings,
+                        });
+                    }
+                    Err(e) => {
+                        eprintln!("MCP socket listener error: {}", e);
+                    }
                 }
             });
 
             // Event forwarding: broadcast channel -> Tauri events + DB persistence
+            worker_manager.spawn(EventForwarderWorker {
+                rx,
+                db: Arc::clone(&state_manager),
+                handle,
+            });
+
+            worker_manager.spawn(SchedulerWorker {
+                engine: scheduler_engine,
+                state: Arc::clone(&state_manager),
+            });
+
+            // Tracks the timer engine's own tick loop, so a panicked or
+            // wedged loop shows up as a stalled worker rather than an
+            // invisible gap in `list_workers`.
+            worker_manager.spawn(TimerTickWorker { rx: tick_rx });
+
+            // Optional LAN-reachable TCP transport, advertised over mDNS.
+            // A no-op at runtime unless `settings.tcp.enabled` is set.
+            let tcp_state = Arc::clone(&state_manager);
+            let tcp_workers = worker_manager.clone();
+            let tcp_sessions = app.state::<SessionManager>().inner().clone();
+            let tcp_settings = settings.clone();
             tauri::async_runtime::spawn(async move {
-                loop {
-                    match rx.recv().await {
-                        Ok(event) => {
-                            let (event_name, payload) = match &event {
-                                TimerEvent::Started { session } => {
-                                    if let Err(e) = db.save_session(session) {
-                                        eprintln!("Failed to save session: {}", e);
-                                    }
-                                    ("timer:started", serde_json::to_string(session).unwrap())
-                                }
-                                TimerEvent::Tick {
-                                    remaining_secs,
-                                    session,
-                                } => {
-                                    // Update tray tooltip with remaining time
-                                    let mins = remaining_secs / 60;
-                                    let secs = remaining_secs % 60;
-                                    if let Some(tray) = handle.tray_by_id("main") {
-                                        let _ = tray.set_tooltip(Some(&format!(
-                                            "{} - {:02}:{:02}",
-                                            session.label, mins, secs
-                                        )));
-                                    }
-                                    (
-                                        "timer:tick",
-                                        serde_json::json!({
-                                            "remaining_secs": remaining_secs,
-                                            "session": session
-                                        })
-                                        .to_string(),
-                                    )
-                                }
-                                TimerEvent::Completed { session } => {
-                                    if let Some(ended) = &session.ended_at {
-                                        if let Err(e) = db.update_session(
-                                            &session.id,
-                                            &SessionStatus::Completed,
-                                            ended,
-                                        ) {
-                                            eprintln!("Failed to update session: {}", e);
-                                        }
-                                    }
-                                    // Send system notification
-                                    if let Ok(true) =
-                                        handle.notification().permission_state().map(|p| {
-                                            p == tauri_plugin_notification::PermissionState::Granted
-                                        })
-                                    {
-                                        let _ = handle
-                                            .notification()
-                                            .builder()
-                                            .title("Timer Complete!")
-                                            .body(format!("\"{}\" session finished", session.label))
-                                            .show();
-                                    }
-                                    // Reset tray tooltip
-                                    if let Some(tray) = handle.tray_by_id("main") {
-                                        let _ = tray.set_tooltip(Some("PomodoroAI"));
-                                    }
-                                    ("timer:completed", serde_json::to_string(session).unwrap())
-                                }
-                                TimerEvent::Stopped { session } => {
-                                    if let Some(ended) = &session.ended_at {
-                                        if let Err(e) = db.update_session(
-                                            &session.id,
-                                            &SessionStatus::Stopped,
-                                            ended,
-                                        ) {
-                                            eprintln!("Failed to update session: {}", e);
-                                        }
-                                    }
-                                    // Reset tray tooltip
-                                    if let Some(tray) = handle.tray_by_id("main") {
-                                        let _ = tray.set_tooltip(Some("PomodoroAI"));
-                                    }
-                                    ("timer:stopped", serde_json::to_string(session).unwrap())
-                                }
-                            };
-                            let _ = handle.emit(event_name, payload);
-                        }
-                        Err(tokio::sync::broadcast::error::RecvError::Lagged(n)) => {
-                            eprintln!("Event forwarder lagged by {} events", n);
-                        }
-                        Err(tokio::sync::broadcast::error::RecvError::Closed) => {
-                            break;
+                if let Err(e) = mcp::tcp_transport::start_tcp_listener(
+                    tcp_engine,
+                    tcp_state,
+                    tcp_workers,
+                    tcp_sessions,
+                    &tcp_settings,
+                )
+                .await
+                {
+                    eprintln!("MCP TCP listener error: {}", e);
+                }
+            });
+
+            // Optional remote-reachable QUIC transport, the network
+            // counterpart to the Unix socket for agents on other machines.
+            // A no-op at runtime unless `settings.quic.enabled` is set.
+            let quic_state = Arc::clone(&state_manager);
+            let quic_workers = worker_manager.clone();
+            let quic_settings = settings.clone();
+            let quic_require_auth = settings.mcp.require_auth;
+            let quic_token_path = mcp::auth::token_path(&mcp::transport::socket_path(&settings));
+            tauri::async_runtime::spawn(async move {
+                let auth = if quic_require_auth {
+                    match SocketAuth::load_or_create(&quic_token_path).await {
+                        Ok(auth) => Some(Arc::new(auth)),
+                        Err(e) => {
+                            eprintln!("Failed to load MCP auth token for QUIC listener: {}", e);
+                            return;
                         }
                     }
+                } else {
+                    None
+                };
+
+                if let Err(e) = mcp::quic_transport::start_quic_listener(
+                    quic_engine,
+                    quic_state,
+                    quic_workers,
+                    auth,
+                    &quic_settings,
+                )
+                .await
+                {
+                    eprintln!("MCP QUIC listener error: {}", e);
                 }
             });
 
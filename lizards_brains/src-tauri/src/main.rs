@@ -4,7 +4,13 @@ fn main() {
     let args: Vec<String> = std::env::args().collect();
 
     if args.contains(&"--mcp".to_string()) {
-        pomodoro_ai::run_mcp_shim();
+        let remote = args
+            .iter()
+            .find_map(|arg| arg.strip_prefix("--remote=").map(str::to_string));
+        let remote_token_path = args
+            .iter()
+            .find_map(|arg| arg.strip_prefix("--remote-token=").map(str::to_string));
+        pomodoro_ai::run_mcp_shim(remote, remote_token_path);
     } else {
         pomodoro_ai::run_gui();
     }
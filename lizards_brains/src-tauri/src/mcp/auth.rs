@@ -0,0 +1,215 @@
+use hmac::{Hmac, Mac};
+use rand::RngCore;
+use sha2::Sha256;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+use tokio::io::{AsyncBufReadExt, AsyncWriteExt, BufReader};
+
+type HmacSha256 = Hmac<Sha256>;
+
+const NONCE_LEN: usize = 16;
+const SECRET_LEN: usize = 32;
+const HANDSHAKE_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Challenge-response auth for the Unix socket transport: on accept the
+/// server sends a random nonce, the client must reply with
+/// `HMAC-SHA256(secret, nonce)` hex within a short timeout, and the server
+/// recomputes and compares it in constant time before handing the
+/// connection over to `PomodoroMcpService`.
+///
+/// Kept independent of `UnixListener` so the handshake itself can be unit
+/// tested over an in-memory duplex stream.
+pub struct SocketAuth {
+    secret: Vec<u8>,
+}
+
+impl SocketAuth {
+    /// Loads the secret from `token_path`, generating and persisting a new
+    /// random one (with `0600` permissions) if the file doesn't exist yet.
+    /// Only appropriate for the machine that actually hosts the listener -
+    /// a remote client must not "create" a secret the server doesn't know
+    /// about, so it should call `from_file` instead.
+    pub async fn load_or_create(token_path: &Path) -> io::Result<SocketAuth> {
+        match tokio::fs::read(token_path).await {
+            Ok(bytes) => Ok(SocketAuth { secret: bytes }),
+            Err(e) if e.kind() == io::ErrorKind::NotFound => {
+                let mut secret = vec![0u8; SECRET_LEN];
+                rand::thread_rng().fill_bytes(&mut secret);
+
+                if let Some(parent) = token_path.parent() {
+                    tokio::fs::create_dir_all(parent).await?;
+                }
+                tokio::fs::write(token_path, &secret).await?;
+                Self::restrict_permissions(token_path).await?;
+
+                Ok(SocketAuth { secret })
+            }
+            Err(e) => Err(e),
+        }
+    }
+
+    /// Loads the secret from a token file that already exists, erroring
+    /// rather than fabricating a new one. Used by a remote client, which
+    /// must be handed a copy of the *server's* token file - generating its
+    /// own would silently authenticate with the wrong secret and fail every
+    /// handshake.
+    pub async fn from_file(token_path: &Path) -> io::Result<SocketAuth> {
+        let secret = tokio::fs::read(token_path).await.map_err(|e| {
+            io::Error::new(
+                e.kind(),
+                format!(
+                    "failed to read MCP auth token at {}: {} (copy the server's token file here)",
+                    token_path.display(),
+                    e
+                ),
+            )
+        })?;
+        Ok(SocketAuth { secret })
+    }
+
+    #[cfg(unix)]
+    async fn restrict_permissions(token_path: &Path) -> io::Result<()> {
+        use std::os::unix::fs::PermissionsExt;
+        tokio::fs::set_permissions(token_path, std::fs::Permissions::from_mode(0o600)).await
+    }
+
+    #[cfg(not(unix))]
+    async fn restrict_permissions(_token_path: &Path) -> io::Result<()> {
+        Ok(())
+    }
+
+    /// Server side of the handshake: sends a nonce and verifies the peer's
+    /// response. Returns `Ok(true)` on success, `Ok(false)` if the peer
+    /// replied with the wrong digest (or timed out), so the caller can log
+    /// a rejection rather than treat it as a transport error.
+    pub async fn server_handshake<S>(&self, stream: &mut S) -> io::Result<bool>
+    where
+        S: tokio::io::AsyncRead + tokio::io::AsyncWrite + Unpin,
+    {
+        let mut nonce = vec![0u8; NONCE_LEN];
+        rand::thread_rng().fill_bytes(&mut nonce);
+        let nonce_hex = hex::encode(&nonce);
+
+        stream.write_all(nonce_hex.as_bytes()).await?;
+        stream.write_all(b"\n").await?;
+        stream.flush().await?;
+
+        let mut reader = BufReader::new(stream);
+        let mut response_line = String::new();
+        let read = tokio::time::timeout(HANDSHAKE_TIMEOUT, reader.read_line(&mut response_line)).await;
+
+        let response_hex = match read {
+            Ok(Ok(0)) | Err(_) => return Ok(false),
+            Ok(Ok(_)) => response_line.trim(),
+            Ok(Err(e)) => return Err(e),
+        };
+
+        let Ok(response_bytes) = hex::decode(response_hex) else {
+            return Ok(false);
+        };
+
+        let mut mac = HmacSha256::new_from_slice(&self.secret).expect("HMAC accepts any key length");
+        mac.update(&nonce);
+        Ok(mac.verify_slice(&response_bytes).is_ok())
+    }
+
+    /// Client side of the handshake: reads the server's nonce and replies
+    /// with `HMAC-SHA256(secret, nonce)` hex.
+    pub async fn client_handshake<S>(&self, stream: &mut S) -> io::Result<()>
+    where
+        S: tokio::io::AsyncRead + tokio::io::AsyncWrite + Unpin,
+    {
+        let mut reader = BufReader::new(stream);
+        let mut nonce_line = String::new();
+        tokio::time::timeout(HANDSHAKE_TIMEOUT, reader.read_line(&mut nonce_line))
+            .await
+            .map_err(|_| io::Error::new(io::ErrorKind::TimedOut, "auth handshake timed out"))??;
+
+        let nonce = hex::decode(nonce_line.trim())
+            .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+
+        let mut mac = HmacSha256::new_from_slice(&self.secret).expect("HMAC accepts any key length");
+        mac.update(&nonce);
+        let response_hex = hex::encode(mac.finalize().into_bytes());
+
+        let stream = reader.into_inner();
+        stream.write_all(response_hex.as_bytes()).await?;
+        stream.write_all(b"\n").await?;
+        stream.flush().await
+    }
+}
+
+/// Default token filename, placed alongside the socket file.
+pub fn token_path(socket_path: &Path) -> PathBuf {
+    socket_path
+        .parent()
+        .map(|dir| dir.join("token"))
+        .unwrap_or_else(|| PathBuf::from("token"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tokio::io::duplex;
+
+    fn test_auth(secret: &[u8]) -> SocketAuth {
+        SocketAuth {
+            secret: secret.to_vec(),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_handshake_succeeds_with_matching_secret() {
+        let (mut server_side, mut client_side) = duplex(256);
+        let server_auth = test_auth(b"shared-secret");
+        let client_auth = test_auth(b"shared-secret");
+
+        let server = tokio::spawn(async move { server_auth.server_handshake(&mut server_side).await });
+        client_auth.client_handshake(&mut client_side).await.unwrap();
+
+        assert!(server.await.unwrap().unwrap());
+    }
+
+    #[tokio::test]
+    async fn test_handshake_fails_with_wrong_secret() {
+        let (mut server_side, mut client_side) = duplex(256);
+        let server_auth = test_auth(b"shared-secret");
+        let client_auth = test_auth(b"wrong-secret");
+
+        let server = tokio::spawn(async move { server_auth.server_handshake(&mut server_side).await });
+        client_auth.client_handshake(&mut client_side).await.unwrap();
+
+        assert!(!server.await.unwrap().unwrap());
+    }
+
+    #[tokio::test]
+    async fn test_handshake_fails_on_garbage_response() {
+        let (mut server_side, mut client_side) = duplex(256);
+        let server_auth = test_auth(b"shared-secret");
+
+        let server = tokio::spawn(async move { server_auth.server_handshake(&mut server_side).await });
+        client_side.write_all(b"not-hex\n").await.unwrap();
+
+        assert!(!server.await.unwrap().unwrap());
+    }
+
+    #[tokio::test]
+    async fn test_load_or_create_persists_and_reuses_secret() {
+        let dir = std::env::temp_dir().join(format!("pomodoro-auth-test-{}", uuid::Uuid::new_v4()));
+        let token_path = dir.join("token");
+
+        let first = SocketAuth::load_or_create(&token_path).await.unwrap();
+        let second = SocketAuth::load_or_create(&token_path).await.unwrap();
+        assert_eq!(first.secret, second.secret);
+
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            let mode = std::fs::metadata(&token_path).unwrap().permissions().mode();
+            assert_eq!(mode & 0o777, 0o600);
+        }
+
+        std::fs::remove_dir_all(&dir).ok();
+    }
+}
@@ -0,0 +1,279 @@
+use std::io;
+use std::time::Duration;
+use tokio::io::{
+    duplex, split, AsyncBufReadExt, AsyncRead, AsyncReadExt, AsyncWrite, AsyncWriteExt, BufReader,
+    DuplexStream,
+};
+
+/// Compression codec negotiated for framed JSON-RPC messages once the auth
+/// handshake completes. `None` is the default so a peer that skips the
+/// capability line keeps working uncompressed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Codec {
+    None,
+    Zstd,
+}
+
+impl Codec {
+    fn as_str(&self) -> &'static str {
+        match self {
+            Codec::None => "none",
+            Codec::Zstd => "zstd",
+        }
+    }
+
+    fn parse(s: &str) -> Option<Codec> {
+        match s {
+            "none" => Some(Codec::None),
+            "zstd" => Some(Codec::Zstd),
+            _ => None,
+        }
+    }
+}
+
+const ZSTD_LEVEL: i32 = 3;
+const HANDSHAKE_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Largest frame `read_frame` will allocate a buffer for. JSON-RPC payloads
+/// for this service are small (session/status objects); a peer claiming a
+/// frame larger than this bound is either broken or hostile, so the length
+/// prefix is rejected outright instead of driving an unbounded allocation.
+const MAX_FRAME_LEN: usize = 16 * 1024 * 1024;
+
+/// Server side of the capability handshake: advertises the codecs it
+/// supports and waits for the peer's choice. Falls back to `Codec::None`
+/// if the peer omits the reply line or the read times out, so an older
+/// client that doesn't know about this handshake still gets served
+/// (uncompressed) rather than rejected.
+pub async fn negotiate_codec_server<S>(stream: &mut S) -> io::Result<Codec>
+where
+    S: AsyncRead + AsyncWrite + Unpin,
+{
+    stream.write_all(b"codecs: none,zstd\n").await?;
+    stream.flush().await?;
+
+    // Read byte by byte: the peer may send its first frame right behind the
+    // choice line, and a buffered reader dropped here would swallow it.
+    let read = tokio::time::timeout(HANDSHAKE_TIMEOUT, async {
+        let mut line = Vec::new();
+        loop {
+            let byte = stream.read_u8().await?;
+            line.push(byte);
+            if byte == b'\n' {
+                return Ok::<_, io::Error>(String::from_utf8_lossy(&line).into_owned());
+            }
+        }
+    })
+    .await;
+
+    match read {
+        Ok(Ok(line)) => Ok(parse_choice(&line).unwrap_or(Codec::None)),
+        _ => Ok(Codec::None),
+    }
+}
+
+/// Client side of the capability handshake: reads the peer's advertised
+/// codecs and echoes back `zstd` if offered, `none` otherwise.
+pub async fn negotiate_codec_client<S>(stream: &mut S) -> io::Result<Codec>
+where
+    S: AsyncRead + AsyncWrite + Unpin,
+{
+    let mut reader = BufReader::new(stream);
+    let mut line = String::new();
+    tokio::time::timeout(HANDSHAKE_TIMEOUT, reader.read_line(&mut line))
+        .await
+        .map_err(|_| io::Error::new(io::ErrorKind::TimedOut, "codec handshake timed out"))??;
+
+    let chosen = if parse_advertised(&line).contains(&Codec::Zstd) {
+        Codec::Zstd
+    } else {
+        Codec::None
+    };
+
+    let stream = reader.into_inner();
+    stream
+        .write_all(format!("codec: {}\n", chosen.as_str()).as_bytes())
+        .await?;
+    stream.flush().await?;
+    Ok(chosen)
+}
+
+fn parse_choice(line: &str) -> Option<Codec> {
+    let value = line.trim().strip_prefix("codec:")?.trim();
+    Codec::parse(value)
+}
+
+fn parse_advertised(line: &str) -> Vec<Codec> {
+    let Some(value) = line.trim().strip_prefix("codecs:") else {
+        return Vec::new();
+    };
+    value
+        .split(',')
+        .filter_map(|s| Codec::parse(s.trim()))
+        .collect()
+}
+
+/// Writes one length-prefixed frame: a big-endian `u32` byte length
+/// followed by `payload`, zstd-compressed first when `codec` is `Zstd`.
+pub async fn write_frame<W>(writer: &mut W, codec: Codec, payload: &[u8]) -> io::Result<()>
+where
+    W: AsyncWrite + Unpin,
+{
+    let encoded = match codec {
+        Codec::None => payload.to_vec(),
+        Codec::Zstd => zstd::stream::encode_all(payload, ZSTD_LEVEL)?,
+    };
+    writer.write_u32(encoded.len() as u32).await?;
+    writer.write_all(&encoded).await?;
+    writer.flush().await
+}
+
+/// Reads one length-prefixed frame written by `write_frame`, decompressing
+/// it first when `codec` is `Zstd`.
+pub async fn read_frame<R>(reader: &mut R, codec: Codec) -> io::Result<Vec<u8>>
+where
+    R: AsyncRead + Unpin,
+{
+    let len = reader.read_u32().await? as usize;
+    if len > MAX_FRAME_LEN {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidData,
+            format!("frame length {} exceeds max of {} bytes", len, MAX_FRAME_LEN),
+        ));
+    }
+    let mut buf = vec![0u8; len];
+    reader.read_exact(&mut buf).await?;
+    match codec {
+        Codec::None => Ok(buf),
+        Codec::Zstd => zstd::stream::decode_all(&buf[..]),
+    }
+}
+
+/// Wraps `socket` so callers (e.g. `PomodoroMcpService::serve`) see a plain
+/// newline-delimited stream, while length-prefixed, optionally
+/// zstd-compressed frames are exchanged with the real peer on background
+/// tasks. Each outgoing JSON-RPC line becomes one frame; each incoming
+/// frame becomes one JSON-RPC line.
+pub fn wrap_stream<S>(socket: S, codec: Codec) -> DuplexStream
+where
+    S: AsyncRead + AsyncWrite + Unpin + Send + 'static,
+{
+    let (app_side, net_side) = duplex(64 * 1024);
+    let (net_read, mut net_write) = split(net_side);
+    let (socket_read, mut socket_write) = split(socket);
+
+    tokio::spawn(async move {
+        let mut lines = BufReader::new(net_read).lines();
+        loop {
+            match lines.next_line().await {
+                Ok(Some(line)) => {
+                    if write_frame(&mut socket_write, codec, line.as_bytes())
+                        .await
+                        .is_err()
+                    {
+                        break;
+                    }
+                }
+                _ => break,
+            }
+        }
+    });
+
+    tokio::spawn(async move {
+        let mut socket_read = socket_read;
+        loop {
+            let payload = match read_frame(&mut socket_read, codec).await {
+                Ok(payload) => payload,
+                Err(_) => break,
+            };
+            if net_write.write_all(&payload).await.is_err() {
+                break;
+            }
+            if net_write.write_all(b"\n").await.is_err() {
+                break;
+            }
+            if net_write.flush().await.is_err() {
+                break;
+            }
+        }
+    });
+
+    app_side
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::timer::{Origin, Session, SessionKind, SessionStatus};
+    use tokio::io::duplex;
+
+    #[tokio::test]
+    async fn test_round_trip_uncompressed_session() {
+        let session = Session {
+            id: "abc123".into(),
+            label: "Write tests".into(),
+            duration_secs: 1500,
+            started_at: "2026-01-01T00:00:00Z".into(),
+            ended_at: None,
+            origin: Origin::Human,
+            status: SessionStatus::Running,
+            kind: SessionKind::Work,
+            cycle_index: 0,
+        };
+        let payload = serde_json::to_vec(&session).unwrap();
+
+        let (mut a, mut b) = duplex(4096);
+        write_frame(&mut a, Codec::None, &payload).await.unwrap();
+        let received = read_frame(&mut b, Codec::None).await.unwrap();
+
+        assert_eq!(received, payload);
+        let decoded: Session = serde_json::from_slice(&received).unwrap();
+        assert_eq!(decoded.id, session.id);
+    }
+
+    #[tokio::test]
+    async fn test_round_trip_zstd_compressed() {
+        let payload = serde_json::to_vec(&serde_json::json!({
+            "event": "timer:completed",
+            "session": { "id": "abc123", "label": "Write tests" },
+        }))
+        .unwrap();
+
+        let (mut a, mut b) = duplex(4096);
+        write_frame(&mut a, Codec::Zstd, &payload).await.unwrap();
+        let received = read_frame(&mut b, Codec::Zstd).await.unwrap();
+
+        assert_eq!(received, payload);
+    }
+
+    #[tokio::test]
+    async fn test_negotiate_picks_zstd_when_advertised() {
+        let (mut server_side, mut client_side) = duplex(256);
+
+        let server = tokio::spawn(async move { negotiate_codec_server(&mut server_side).await });
+        let client_codec = negotiate_codec_client(&mut client_side).await.unwrap();
+
+        assert_eq!(client_codec, Codec::Zstd);
+        assert_eq!(server.await.unwrap().unwrap(), Codec::Zstd);
+    }
+
+    #[tokio::test]
+    async fn test_read_frame_rejects_oversized_length_prefix() {
+        let (mut a, mut b) = duplex(64);
+        a.write_u32((MAX_FRAME_LEN + 1) as u32).await.unwrap();
+
+        let result = read_frame(&mut b, Codec::None).await;
+
+        assert_eq!(result.unwrap_err().kind(), io::ErrorKind::InvalidData);
+    }
+
+    #[tokio::test]
+    async fn test_negotiate_falls_back_to_none_when_peer_is_silent() {
+        let (mut server_side, client_side) = duplex(256);
+
+        let server = tokio::spawn(async move { negotiate_codec_server(&mut server_side).await });
+        drop(client_side);
+
+        assert_eq!(server.await.unwrap().unwrap(), Codec::None);
+    }
+}
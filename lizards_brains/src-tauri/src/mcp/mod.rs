@@ -0,0 +1,7 @@
+pub mod auth;
+pub mod framing;
+pub mod quic_transport;
+pub mod session;
+pub mod tcp_transport;
+pub mod tools;
+pub mod transport;
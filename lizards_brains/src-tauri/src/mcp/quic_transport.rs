@@ -0,0 +1,267 @@
+use crate::mcp::auth::SocketAuth;
+use crate::mcp::framing;
+use crate::mcp::tools::PomodoroMcpService;
+use crate::settings::Settings;
+use crate::state::StateManager;
+use crate::timer::TimerEngine;
+use crate::worker::WorkerManager;
+use quinn::{ClientConfig, Endpoint, ServerConfig};
+use rmcp::ServiceExt;
+use std::net::SocketAddr;
+use std::sync::Arc;
+
+/// ALPN protocol id negotiated for the PomodoroAI MCP service over QUIC.
+const ALPN: &[u8] = b"pomodoro-mcp";
+
+/// Starts the optional QUIC transport: binds `settings.quic.bind_addr` with
+/// a self-signed certificate, and serves each accepted bidirectional stream
+/// with the same `PomodoroMcpService`, auth handshake, and codec negotiation
+/// the Unix socket and TCP transports use. A no-op when `settings.quic.enabled`
+/// is false.
+pub async fn start_quic_listener(
+    engine: TimerEngine,
+    state: Arc<StateManager>,
+    workers: WorkerManager,
+    auth: Option<Arc<SocketAuth>>,
+    settings: &Settings,
+) -> Result<(), Box<dyn std::error::Error>> {
+    if !settings.quic.enabled {
+        return Ok(());
+    }
+
+    let addr: SocketAddr = settings.quic.bind_addr.parse()?;
+    start_quic_listener_on(addr, engine, state, workers, auth, settings.clone()).await
+}
+
+/// Binds a QUIC endpoint at `addr` and serves MCP connections until the
+/// endpoint is closed or accept fails.
+pub async fn start_quic_listener_on(
+    addr: SocketAddr,
+    engine: TimerEngine,
+    state: Arc<StateManager>,
+    workers: WorkerManager,
+    auth: Option<Arc<SocketAuth>>,
+    settings: Settings,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let endpoint = Endpoint::server(server_config()?, addr)?;
+    eprintln!("MCP QUIC listener started on {}", addr);
+
+    while let Some(incoming) = endpoint.accept().await {
+        let engine = engine.clone();
+        let state = Arc::clone(&state);
+        let workers = workers.clone();
+        let auth = auth.clone();
+        let settings = settings.clone();
+        tokio::spawn(async move {
+            match incoming.await {
+                Ok(connection) => {
+                    serve_connection(connection, engine, state, workers, auth, settings).await
+                }
+                Err(e) => eprintln!("MCP QUIC connection error: {}", e),
+            }
+        });
+    }
+
+    Ok(())
+}
+
+/// Accepts every bidirectional stream opened on `connection` and serves each
+/// as an independent MCP session, since a QUIC connection can multiplex
+/// many of them.
+async fn serve_connection(
+    connection: quinn::Connection,
+    engine: TimerEngine,
+    state: Arc<StateManager>,
+    workers: WorkerManager,
+    auth: Option<Arc<SocketAuth>>,
+    settings: Settings,
+) {
+    loop {
+        let (send, recv) = match connection.accept_bi().await {
+            Ok(stream) => stream,
+            Err(e) => {
+                eprintln!("MCP QUIC stream error: {}", e);
+                break;
+            }
+        };
+
+        let engine = engine.clone();
+        let state = Arc::clone(&state);
+        let workers = workers.clone();
+        let auth = auth.clone();
+        let settings = settings.clone();
+        tokio::spawn(async move {
+            let mut stream = tokio::io::join(recv, send);
+
+            if let Some(auth) = &auth {
+                match auth.server_handshake(&mut stream).await {
+                    Ok(true) => {}
+                    Ok(false) => {
+                        eprintln!("MCP QUIC client rejected: failed auth handshake");
+                        return;
+                    }
+                    Err(e) => {
+                        eprintln!("MCP QUIC auth handshake error: {}", e);
+                        return;
+                    }
+                }
+            }
+
+            let codec = match framing::negotiate_codec_server(&mut stream).await {
+                Ok(codec) => codec,
+                Err(e) => {
+                    eprintln!("MCP QUIC codec handshake error: {}", e);
+                    return;
+                }
+            };
+            let stream = framing::wrap_stream(stream, codec);
+
+            let service = PomodoroMcpService {
+                engine,
+                state,
+                workers,
+                settings,
+            };
+            match service.serve(stream).await {
+                Ok(running) => {
+                    let _ = running.waiting().await;
+                }
+                Err(e) => {
+                    eprintln!("MCP QUIC session error: {}", e);
+                }
+            }
+        });
+    }
+}
+
+/// Dials `addr` over QUIC, runs the auth handshake if `auth` is set, and
+/// proxies stdin/stdout over the resulting stream - the remote counterpart
+/// to `transport::run_mcp_shim`'s Unix socket connection.
+pub async fn run_shim(addr: &str, auth: Option<&SocketAuth>) -> Result<(), Box<dyn std::error::Error>> {
+    let remote: SocketAddr = addr.parse()?;
+    let mut endpoint = Endpoint::client("0.0.0.0:0".parse()?)?;
+    endpoint.set_default_client_config(client_config());
+
+    let connection = endpoint.connect(remote, "pomodoroai")?.await?;
+    let (send, recv) = connection.open_bi().await?;
+    let mut stream = tokio::io::join(recv, send);
+
+    if let Some(auth) = auth {
+        auth.client_handshake(&mut stream).await?;
+    }
+
+    let codec = framing::negotiate_codec_client(&mut stream).await?;
+    let mut stream = framing::wrap_stream(stream, codec);
+    let mut stdio = tokio::io::join(tokio::io::stdin(), tokio::io::stdout());
+
+    // `copy_bidirectional` shuts down each direction independently on
+    // half-close, so a response that's still streaming out isn't truncated
+    // just because stdin (or the QUIC stream) closed first.
+    tokio::io::copy_bidirectional(&mut stdio, &mut stream).await?;
+
+    Ok(())
+}
+
+/// Self-signed certificate server config. There is no CA-issued cert for an
+/// ad hoc LAN/remote listener, so the client config below skips verification
+/// instead - the HMAC handshake is what actually authenticates the peer.
+fn server_config() -> Result<ServerConfig, Box<dyn std::error::Error>> {
+    let cert = rcgen::generate_simple_self_signed(vec!["pomodoroai".into()])?;
+    let cert_der = cert.cert.der().clone();
+    let key_der = rustls::pki_types::PrivateKeyDer::try_from(cert.signing_key.serialize_der())
+        .map_err(|e| format!("invalid generated key: {}", e))?;
+
+    let mut crypto = rustls::ServerConfig::builder()
+        .with_no_client_auth()
+        .with_single_cert(vec![cert_der], key_der)?;
+    crypto.alpn_protocols = vec![ALPN.to_vec()];
+
+    let mut server_config = ServerConfig::with_crypto(Arc::new(
+        quinn::crypto::rustls::QuicServerConfig::try_from(crypto)?,
+    ));
+    if let Some(transport) = Arc::get_mut(&mut server_config.transport) {
+        transport.max_concurrent_bidi_streams(64_u32.into());
+    }
+    Ok(server_config)
+}
+
+fn client_config() -> ClientConfig {
+    let mut crypto = rustls::ClientConfig::builder()
+        .dangerous()
+        .with_custom_certificate_verifier(Arc::new(skip_verification::SkipServerVerification))
+        .with_no_client_auth();
+    crypto.alpn_protocols = vec![ALPN.to_vec()];
+    ClientConfig::new(Arc::new(
+        quinn::crypto::rustls::QuicClientConfig::try_from(crypto).expect("rustls config is valid"),
+    ))
+}
+
+/// A QUIC connection to an ad hoc, self-signed PomodoroAI endpoint has no
+/// real CA chain to verify against; the HMAC challenge-response handshake
+/// is the actual authentication boundary, so certificate verification is
+/// intentionally skipped here rather than pinned to a cert we'd have to
+/// distribute out of band.
+mod skip_verification {
+    use rustls::client::danger::{HandshakeSignatureValid, ServerCertVerified, ServerCertVerifier};
+    use rustls::pki_types::{CertificateDer, ServerName, UnixTime};
+
+    #[derive(Debug)]
+    pub struct SkipServerVerification;
+
+    impl ServerCertVerifier for SkipServerVerification {
+        fn verify_server_cert(
+            &self,
+            _end_entity: &CertificateDer<'_>,
+            _intermediates: &[CertificateDer<'_>],
+            _server_name: &ServerName<'_>,
+            _ocsp_response: &[u8],
+            _now: UnixTime,
+        ) -> Result<ServerCertVerified, rustls::Error> {
+            Ok(ServerCertVerified::assertion())
+        }
+
+        fn verify_tls12_signature(
+            &self,
+            _message: &[u8],
+            _cert: &CertificateDer<'_>,
+            _dss: &rustls::DigitallySignedStruct,
+        ) -> Result<HandshakeSignatureValid, rustls::Error> {
+            Ok(HandshakeSignatureValid::assertion())
+        }
+
+        fn verify_tls13_signature(
+            &self,
+            _message: &[u8],
+            _cert: &CertificateDer<'_>,
+            _dss: &rustls::DigitallySignedStruct,
+        ) -> Result<HandshakeSignatureValid, rustls::Error> {
+            Ok(HandshakeSignatureValid::assertion())
+        }
+
+        fn supported_verify_schemes(&self) -> Vec<rustls::SignatureScheme> {
+            rustls::crypto::ring::default_provider()
+                .signature_verification_algorithms
+                .supported_schemes()
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_disabled_by_default_returns_immediately() {
+        let engine = TimerEngine::new();
+        let state = Arc::new(StateManager::in_memory().unwrap());
+        let result = start_quic_listener(
+            engine,
+            state,
+            WorkerManager::new(),
+            None,
+            &Settings::default(),
+        )
+        .await;
+        assert!(result.is_ok());
+    }
+}
@@ -0,0 +1,126 @@
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+use tokio::sync::{mpsc, Mutex};
+
+pub type SessionId = u64;
+
+/// A command a `SessionManager` can send to a live connection's serving task.
+pub enum SessionCommand {
+    Disconnect,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SessionInfo {
+    pub id: SessionId,
+    pub peer_addr: String,
+}
+
+struct SessionEntry {
+    peer_addr: String,
+    command_tx: mpsc::Sender<SessionCommand>,
+}
+
+/// Tracks network-connected MCP clients (currently the TCP/mDNS transport),
+/// keyed by a monotonic connection id, so the GUI or an agent can see who is
+/// connected and force a disconnect.
+#[derive(Clone, Default)]
+pub struct SessionManager {
+    sessions: Arc<Mutex<HashMap<SessionId, SessionEntry>>>,
+    next_id: Arc<AtomicU64>,
+}
+
+impl SessionManager {
+    pub fn new() -> Self {
+        SessionManager::default()
+    }
+
+    /// Registers a newly accepted connection and returns its id along with
+    /// the receiver end of its command channel, which the caller's serving
+    /// task should poll alongside the connection itself.
+    pub async fn register(&self, peer_addr: String) -> (SessionId, mpsc::Receiver<SessionCommand>) {
+        let id = self.next_id.fetch_add(1, Ordering::SeqCst);
+        let (command_tx, command_rx) = mpsc::channel(1);
+        self.sessions
+            .lock()
+            .await
+            .insert(id, SessionEntry { peer_addr, command_tx });
+        (id, command_rx)
+    }
+
+    pub async fn unregister(&self, id: SessionId) {
+        self.sessions.lock().await.remove(&id);
+    }
+
+    pub async fn list(&self) -> Vec<SessionInfo> {
+        let sessions = self.sessions.lock().await;
+        let mut infos: Vec<SessionInfo> = sessions
+            .iter()
+            .map(|(id, entry)| SessionInfo {
+                id: *id,
+                peer_addr: entry.peer_addr.clone(),
+            })
+            .collect();
+        infos.sort_by_key(|info| info.id);
+        infos
+    }
+
+    /// Asks the session's serving task to close the connection. Returns
+    /// `false` if no session with this id is currently registered.
+    pub async fn disconnect(&self, id: SessionId) -> bool {
+        let command_tx = {
+            let sessions = self.sessions.lock().await;
+            match sessions.get(&id) {
+                Some(entry) => entry.command_tx.clone(),
+                None => return false,
+            }
+        };
+        command_tx.send(SessionCommand::Disconnect).await.is_ok()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_register_assigns_increasing_ids() {
+        let manager = SessionManager::new();
+        let (id1, _rx1) = manager.register("127.0.0.1:1".to_string()).await;
+        let (id2, _rx2) = manager.register("127.0.0.1:2".to_string()).await;
+        assert!(id2 > id1);
+    }
+
+    #[tokio::test]
+    async fn test_list_reflects_registered_sessions() {
+        let manager = SessionManager::new();
+        let (id, _rx) = manager.register("127.0.0.1:9".to_string()).await;
+        let sessions = manager.list().await;
+        assert_eq!(sessions.len(), 1);
+        assert_eq!(sessions[0].id, id);
+        assert_eq!(sessions[0].peer_addr, "127.0.0.1:9");
+    }
+
+    #[tokio::test]
+    async fn test_unregister_removes_session() {
+        let manager = SessionManager::new();
+        let (id, _rx) = manager.register("127.0.0.1:9".to_string()).await;
+        manager.unregister(id).await;
+        assert!(manager.list().await.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_disconnect_unknown_session_returns_false() {
+        let manager = SessionManager::new();
+        assert!(!manager.disconnect(999).await);
+    }
+
+    #[tokio::test]
+    async fn test_disconnect_sends_command() {
+        let manager = SessionManager::new();
+        let (id, mut rx) = manager.register("127.0.0.1:9".to_string()).await;
+        assert!(manager.disconnect(id).await);
+        assert!(matches!(rx.recv().await, Some(SessionCommand::Disconnect)));
+    }
+}
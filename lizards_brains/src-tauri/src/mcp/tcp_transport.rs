@@ -0,0 +1,164 @@
+use crate::mcp::auth::SocketAuth;
+use crate::mcp::framing;
+use crate::mcp::session::SessionManager;
+use crate::mcp::tools::PomodoroMcpService;
+use crate::settings::Settings;
+use crate::state::StateManager;
+use crate::timer::TimerEngine;
+use crate::worker::WorkerManager;
+use rmcp::ServiceExt;
+use std::net::SocketAddr;
+use std::sync::Arc;
+use tokio::net::{TcpListener, TcpStream};
+
+/// mDNS/DNS-SD service type PomodoroAI advertises itself under, so LAN
+/// agents can discover the service without knowing its host/port ahead of
+/// time.
+const MDNS_SERVICE_TYPE: &str = "_pomodoro-mcp._tcp";
+
+/// Starts the optional TCP transport: binds `settings.tcp.bind_addr`,
+/// advertises it over mDNS, and serves each accepted connection with the
+/// same `PomodoroMcpService` the Unix socket transport uses. A no-op when
+/// `settings.tcp.enabled` is false.
+pub async fn start_tcp_listener(
+    engine: TimerEngine,
+    state: Arc<StateManager>,
+    workers: WorkerManager,
+    sessions: SessionManager,
+    settings: &Settings,
+) -> Result<(), Box<dyn std::error::Error>> {
+    if !settings.tcp.enabled {
+        return Ok(());
+    }
+
+    let auth = if settings.mcp.require_auth {
+        let path = crate::mcp::transport::socket_path(settings);
+        Some(Arc::new(
+            SocketAuth::load_or_create(&crate::mcp::auth::token_path(&path)).await?,
+        ))
+    } else {
+        None
+    };
+
+    let listener = TcpListener::bind(&settings.tcp.bind_addr).await?;
+    let local_addr = listener.local_addr()?;
+    eprintln!("MCP TCP listener started on {}", local_addr);
+
+    let responder = libmdns::Responder::new()?;
+    let _mdns_guard = responder.register(
+        MDNS_SERVICE_TYPE.to_string(),
+        settings.tcp.mdns_service_name.clone(),
+        local_addr.port(),
+        &["path=/"],
+    );
+
+    loop {
+        let (stream, peer_addr) = listener.accept().await?;
+        accept_one(
+            stream,
+            peer_addr,
+            &engine,
+            &state,
+            &workers,
+            &sessions,
+            auth.as_ref(),
+            settings,
+        )
+        .await;
+    }
+}
+
+/// Registers the connection in `sessions`, then spawns a task that runs the
+/// auth handshake (if `auth` is set) and the codec handshake, and serves it
+/// until the client disconnects or a `SessionCommand::Disconnect` arrives.
+async fn accept_one(
+    mut stream: TcpStream,
+    peer_addr: SocketAddr,
+    engine: &TimerEngine,
+    state: &Arc<StateManager>,
+    workers: &WorkerManager,
+    sessions: &SessionManager,
+    auth: Option<&Arc<SocketAuth>>,
+    settings: &Settings,
+) {
+    let engine = engine.clone();
+    let state = Arc::clone(state);
+    let workers = workers.clone();
+    let sessions = sessions.clone();
+    let auth = auth.cloned();
+    let settings = settings.clone();
+    let (session_id, mut command_rx) = sessions.register(peer_addr.to_string()).await;
+
+    tokio::spawn(async move {
+        if let Some(auth) = &auth {
+            match auth.server_handshake(&mut stream).await {
+                Ok(true) => {}
+                Ok(false) => {
+                    eprintln!("MCP TCP client rejected ({}): failed auth handshake", peer_addr);
+                    sessions.unregister(session_id).await;
+                    return;
+                }
+                Err(e) => {
+                    eprintln!("MCP TCP auth handshake error ({}): {}", peer_addr, e);
+                    sessions.unregister(session_id).await;
+                    return;
+                }
+            }
+        }
+
+        let codec = match framing::negotiate_codec_server(&mut stream).await {
+            Ok(codec) => codec,
+            Err(e) => {
+                eprintln!("MCP TCP codec handshake error ({}): {}", peer_addr, e);
+                sessions.unregister(session_id).await;
+                return;
+            }
+        };
+        let stream = framing::wrap_stream(stream, codec);
+
+        let service = PomodoroMcpService {
+            engine,
+            state,
+            workers,
+            settings,
+        };
+
+        tokio::select! {
+            result = service.serve(stream) => {
+                match result {
+                    Ok(running) => {
+                        let _ = running.waiting().await;
+                    }
+                    Err(e) => {
+                        eprintln!("MCP TCP session error ({}): {}", peer_addr, e);
+                    }
+                }
+            }
+            _ = command_rx.recv() => {
+                // Disconnect requested (or the sender was dropped); dropping
+                // `stream` below tears the connection down.
+            }
+        }
+        sessions.unregister(session_id).await;
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_disabled_by_default_returns_immediately() {
+        let engine = TimerEngine::new();
+        let state = Arc::new(StateManager::in_memory().unwrap());
+        let result = start_tcp_listener(
+            engine,
+            state,
+            WorkerManager::new(),
+            SessionManager::new(),
+            &Settings::default(),
+        )
+        .await;
+        assert!(result.is_ok());
+    }
+}
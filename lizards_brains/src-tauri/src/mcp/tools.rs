@@ -1,20 +1,29 @@
+use crate::settings::Settings;
 use crate::state::StateManager;
-use crate::timer::{Origin, TimerEngine};
+use crate::timer::{parse_duration_secs, CycleConfig, Origin, ScheduledSession, TimerEngine};
+use crate::worker::WorkerManager;
 use rmcp::model::{ServerCapabilities, ServerInfo};
 use rmcp::{schemars, tool, ServerHandler};
 use serde::{Deserialize, Serialize};
 use std::sync::Arc;
+use uuid::Uuid;
 
 #[derive(Clone)]
 pub struct PomodoroMcpService {
     pub engine: TimerEngine,
     pub state: Arc<StateManager>,
+    pub workers: WorkerManager,
+    pub settings: Settings,
 }
 
 #[derive(Debug, Serialize, Deserialize, schemars::JsonSchema)]
 pub struct StartTimerParams {
-    /// Duration in minutes (1-1440)
-    pub duration_minutes: u32,
+    /// Duration in minutes (1-1440). Defaults to `timer.default_work_minutes`
+    /// when omitted.
+    pub duration_minutes: Option<u32>,
+    /// Human-friendly duration, e.g. "25m", "1h30m", "90s". Takes precedence
+    /// over `duration_minutes` when present.
+    pub duration: Option<String>,
     /// Label for this focus session (1-64 chars)
     pub label: String,
 }
@@ -27,22 +36,101 @@ pub struct GetHistoryParams {
     pub end_date: Option<String>,
 }
 
+#[derive(Debug, Serialize, Deserialize, schemars::JsonSchema)]
+pub struct ScheduleTimerParams {
+    /// Label for the scheduled focus session (1-64 chars)
+    pub label: String,
+    /// Human-friendly session duration, e.g. "25m", "1h30m"
+    pub duration: String,
+    /// RFC 3339 timestamp of the first run, e.g. "2026-07-30T09:00:00Z"
+    pub start_at: String,
+    /// Optional human-friendly recurrence interval (e.g. "24h") to repeat
+    /// the session after each run; omit for a one-shot.
+    pub recurrence: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, schemars::JsonSchema)]
+pub struct StartCycleParams {
+    /// Label for this focus session (1-64 chars)
+    pub label: String,
+    /// Work interval duration in minutes (1-1440). Defaults to
+    /// `timer.default_work_minutes` when omitted.
+    pub work_minutes: Option<u32>,
+    /// Short break duration in minutes (1-1440). Defaults to
+    /// `timer.default_break_minutes` when omitted.
+    pub short_break_minutes: Option<u32>,
+    /// Long break duration in minutes (1-1440). Defaults to
+    /// `timer.default_long_break_minutes` when omitted.
+    pub long_break_minutes: Option<u32>,
+    /// Number of work sessions per set before a long break. Defaults to
+    /// `timer.default_sessions_before_long_break` when omitted.
+    pub sessions_before_long_break: Option<u32>,
+}
+
 #[tool(tool_box)]
 impl PomodoroMcpService {
     #[tool(
         description = "Start a new Pomodoro focus timer. Only one timer can be active at a time."
     )]
     async fn start_timer(&self, #[tool(aggr)] params: StartTimerParams) -> String {
-        match self
-            .engine
-            .start(params.duration_minutes, &params.label, Origin::Agent)
-            .await
-        {
+        let result = match params.duration {
+            Some(duration) => match parse_duration_secs(&duration) {
+                Ok(duration_secs) => {
+                    self.engine
+                        .start_for_secs(duration_secs, &params.label, Origin::Agent)
+                        .await
+                }
+                Err(e) => Err(e),
+            },
+            None => {
+                let duration_minutes = params
+                    .duration_minutes
+                    .unwrap_or(self.settings.timer.default_work_minutes);
+                self.engine
+                    .start(duration_minutes, &params.label, Origin::Agent)
+                    .await
+            }
+        };
+        match result {
             Ok(session) => serde_json::to_string_pretty(&session).unwrap(),
             Err(e) => format!("Error: {}", e),
         }
     }
 
+    #[tool(
+        description = "Schedule a Pomodoro session to start at a future time, optionally repeating on a fixed interval. A background worker starts it automatically once due."
+    )]
+    async fn schedule_timer(&self, #[tool(aggr)] params: ScheduleTimerParams) -> String {
+        let label = match TimerEngine::validate_label(&params.label) {
+            Ok(label) => label,
+            Err(e) => return format!("Error: {}", e),
+        };
+        let duration_secs = match parse_duration_secs(&params.duration) {
+            Ok(secs) => secs,
+            Err(e) => return format!("Error: {}", e),
+        };
+        let duration_secs = match TimerEngine::validate_duration_secs(duration_secs) {
+            Ok(secs) => secs,
+            Err(e) => return format!("Error: {}", e),
+        };
+        let recurrence_secs = match params.recurrence.as_deref().map(parse_duration_secs).transpose() {
+            Ok(secs) => secs,
+            Err(e) => return format!("Error: {}", e),
+        };
+        let scheduled = ScheduledSession {
+            id: Uuid::new_v4().to_string(),
+            label,
+            duration_secs,
+            origin: Origin::Agent,
+            run_at: params.start_at,
+            recurrence_secs,
+        };
+        match self.state.save_scheduled_session(&scheduled) {
+            Ok(()) => serde_json::to_string_pretty(&scheduled).unwrap(),
+            Err(e) => format!("Error: {}", e),
+        }
+    }
+
     #[tool(description = "Stop the currently running timer.")]
     async fn stop_timer(&self) -> String {
         match self.engine.stop().await {
@@ -51,6 +139,54 @@ impl PomodoroMcpService {
         }
     }
 
+    #[tool(description = "Pause the currently running timer without losing elapsed time.")]
+    async fn pause_timer(&self) -> String {
+        match self.engine.pause().await {
+            Ok(()) => "Paused".to_string(),
+            Err(e) => format!("Error: {}", e),
+        }
+    }
+
+    #[tool(description = "Resume a paused timer from where it left off.")]
+    async fn resume_timer(&self) -> String {
+        match self.engine.resume().await {
+            Ok(()) => "Resumed".to_string(),
+            Err(e) => format!("Error: {}", e),
+        }
+    }
+
+    #[tool(
+        description = "Start an automatic Pomodoro cycle: work sessions loop into short breaks, with a long break every N work sessions, looping until stopped. Resumes the set's position from the last persisted session."
+    )]
+    async fn start_cycle(&self, #[tool(aggr)] params: StartCycleParams) -> String {
+        let resume_cycle_index = match self.state.get_last_session() {
+            Ok(last) => last.map(|s| s.cycle_index).unwrap_or(0),
+            Err(e) => return format!("Error: {}", e),
+        };
+        let config = CycleConfig {
+            work_minutes: params
+                .work_minutes
+                .unwrap_or(self.settings.timer.default_work_minutes),
+            short_break_minutes: params
+                .short_break_minutes
+                .unwrap_or(self.settings.timer.default_break_minutes),
+            long_break_minutes: params
+                .long_break_minutes
+                .unwrap_or(self.settings.timer.default_long_break_minutes),
+            sessions_before_long_break: params
+                .sessions_before_long_break
+                .unwrap_or(self.settings.timer.default_sessions_before_long_break),
+        };
+        match self
+            .engine
+            .start_cycle(config, &params.label, Origin::Agent, resume_cycle_index)
+            .await
+        {
+            Ok(session) => serde_json::to_string_pretty(&session).unwrap(),
+            Err(e) => format!("Error: {}", e),
+        }
+    }
+
     #[tool(description = "Get the current timer status including session info and remaining time.")]
     async fn get_status(&self) -> String {
         let status = self.engine.get_status().await;
@@ -67,6 +203,14 @@ impl PomodoroMcpService {
             Err(e) => format!("Error: {}", e),
         }
     }
+
+    #[tool(
+        description = "List background workers (socket listener, event forwarder, etc.) and whether they are active, idle, or dead."
+    )]
+    async fn list_workers(&self) -> String {
+        let workers = self.workers.list().await;
+        serde_json::to_string_pretty(&workers).unwrap()
+    }
 }
 
 #[tool(tool_box)]
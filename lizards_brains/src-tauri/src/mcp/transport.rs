@@ -1,65 +1,175 @@
+use crate::mcp::auth::{self, SocketAuth};
+use crate::mcp::framing;
 use crate::mcp::tools::PomodoroMcpService;
+use crate::settings::Settings;
 use crate::state::StateManager;
 use crate::timer::TimerEngine;
+use crate::worker::WorkerManager;
+use rand::Rng;
 use rmcp::ServiceExt;
 use std::path::{Path, PathBuf};
 use std::sync::Arc;
+use std::time::Duration;
 use tokio::net::{UnixListener, UnixStream};
 
-/// Returns the canonical socket path used by both the GUI listener and CLI shim.
-pub fn socket_path() -> PathBuf {
+/// Total time the CLI shim keeps retrying a connection before giving up,
+/// covering the window while the GUI is starting up or restarting.
+const CONNECT_MAX_WAIT: Duration = Duration::from_secs(30);
+const CONNECT_INITIAL_BACKOFF: Duration = Duration::from_millis(200);
+const CONNECT_MAX_BACKOFF: Duration = Duration::from_secs(5);
+
+/// Returns the socket path used by both the GUI listener and CLI shim,
+/// honoring `settings.mcp.socket_path`.
+pub fn socket_path(settings: &Settings) -> PathBuf {
     let data_dir = dirs::data_dir().expect("failed to resolve data directory");
-    data_dir.join("com.pomodoroai.app").join("pomodoro.sock")
+    data_dir
+        .join("com.pomodoroai.app")
+        .join(&settings.mcp.socket_path)
 }
 
-/// Starts a Unix socket listener on the canonical path.
+/// Starts a Unix socket listener on the path resolved from `settings`,
+/// requiring the HMAC auth handshake unless `settings.mcp.require_auth` is
+/// false.
 pub async fn start_socket_listener(
     engine: TimerEngine,
     state: Arc<StateManager>,
+    workers: WorkerManager,
+    settings: &Settings,
 ) -> Result<(), Box<dyn std::error::Error>> {
-    start_socket_listener_on(&socket_path(), engine, state).await
+    let path = socket_path(settings);
+    let auth = if settings.mcp.require_auth {
+        Some(Arc::new(
+            SocketAuth::load_or_create(&auth::token_path(&path)).await?,
+        ))
+    } else {
+        None
+    };
+    start_socket_listener_on(&path, engine, state, workers, auth, settings.clone()).await
 }
 
-/// Starts a Unix socket listener on a given path.
-/// Accepts MCP client connections and serves each one with a fresh PomodoroMcpService.
-pub async fn start_socket_listener_on(
-    path: &Path,
-    engine: TimerEngine,
-    state: Arc<StateManager>,
-) -> Result<(), Box<dyn std::error::Error>> {
-    // Ensure parent directory exists
+/// Binds the Unix socket listener at `path`, removing a stale socket file
+/// left behind by a previous crash.
+pub async fn bind_socket_listener(path: &Path) -> Result<UnixListener, Box<dyn std::error::Error>> {
     if let Some(parent) = path.parent() {
         std::fs::create_dir_all(parent)?;
     }
 
-    // Remove stale socket file from a previous crash
     if path.exists() {
         std::fs::remove_file(path)?;
     }
 
     let listener = UnixListener::bind(path)?;
     eprintln!("MCP socket listener started on {:?}", path);
+    Ok(listener)
+}
 
-    loop {
-        match listener.accept().await {
-            Ok((stream, _addr)) => {
-                let service = PomodoroMcpService {
-                    engine: engine.clone(),
-                    state: Arc::clone(&state),
-                };
-                tokio::spawn(async move {
-                    match service.serve(stream).await {
-                        Ok(running) => {
-                            let _ = running.waiting().await;
-                        }
-                        Err(e) => {
-                            eprintln!("MCP client session error: {}", e);
-                        }
-                    }
-                });
+/// Accepts a single MCP client connection, runs the auth handshake if
+/// `auth` is set, and serves accepted/authenticated connections with a
+/// fresh `PomodoroMcpService` on its own task. Split out of the accept loop
+/// so it can be driven one iteration at a time by `WorkerManager`.
+pub async fn accept_one(
+    listener: &UnixListener,
+    engine: &TimerEngine,
+    state: &Arc<StateManager>,
+    workers: &WorkerManager,
+    auth: Option<&Arc<SocketAuth>>,
+    settings: &Settings,
+) -> std::io::Result<()> {
+    let (mut stream, _addr) = listener.accept().await?;
+    let engine = engine.clone();
+    let state = Arc::clone(state);
+    let workers = workers.clone();
+    let auth = auth.cloned();
+    let settings = settings.clone();
+    tokio::spawn(async move {
+        if let Some(auth) = &auth {
+            match auth.server_handshake(&mut stream).await {
+                Ok(true) => {}
+                Ok(false) => {
+                    eprintln!("MCP client rejected: failed auth handshake");
+                    return;
+                }
+                Err(e) => {
+                    eprintln!("MCP auth handshake error: {}", e);
+                    return;
+                }
+            }
+        }
+
+        let codec = match framing::negotiate_codec_server(&mut stream).await {
+            Ok(codec) => codec,
+            Err(e) => {
+                eprintln!("MCP codec handshake error: {}", e);
+                return;
+            }
+        };
+        let stream = framing::wrap_stream(stream, codec);
+
+        let service = PomodoroMcpService {
+            engine,
+            state,
+            workers,
+            settings,
+        };
+        match service.serve(stream).await {
+            Ok(running) => {
+                let _ = running.waiting().await;
             }
             Err(e) => {
-                eprintln!("Failed to accept MCP connection: {}", e);
+                eprintln!("MCP client session error: {}", e);
+            }
+        }
+    });
+    Ok(())
+}
+
+/// Starts a Unix socket listener on a given path.
+/// Accepts MCP client connections and serves each one with a fresh PomodoroMcpService.
+pub async fn start_socket_listener_on(
+    path: &Path,
+    engine: TimerEngine,
+    state: Arc<StateManager>,
+    workers: WorkerManager,
+    auth: Option<Arc<SocketAuth>>,
+    settings: Settings,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let listener = bind_socket_listener(path).await?;
+
+    loop {
+        if let Err(e) = accept_one(&listener, &engine, &state, &workers, auth.as_ref(), &settings).await {
+            eprintln!("Failed to accept MCP connection: {}", e);
+        }
+    }
+}
+
+/// Connects to the Unix socket at `path`, retrying with jittered
+/// exponential backoff for up to `CONNECT_MAX_WAIT` so the shim rides out
+/// the window while the GUI is starting up or restarting, instead of
+/// failing on the very first attempt.
+async fn connect_with_backoff(path: &Path) -> Result<UnixStream, Box<dyn std::error::Error>> {
+    let deadline = tokio::time::Instant::now() + CONNECT_MAX_WAIT;
+    let mut backoff = CONNECT_INITIAL_BACKOFF;
+    let mut attempt = 0u32;
+
+    loop {
+        attempt += 1;
+        match UnixStream::connect(path).await {
+            Ok(stream) => return Ok(stream),
+            Err(e) => {
+                if tokio::time::Instant::now() >= deadline {
+                    return Err(format!(
+                        "Failed to connect to PomodoroAI socket at {:?} after {} attempts: {}. Is the GUI running?",
+                        path, attempt, e
+                    )
+                    .into());
+                }
+                let jitter = Duration::from_millis(rand::thread_rng().gen_range(0..100));
+                eprintln!(
+                    "MCP shim: socket not ready yet ({}), retrying in {:?} (attempt {})",
+                    e, backoff, attempt
+                );
+                tokio::time::sleep(backoff + jitter).await;
+                backoff = (backoff * 2).min(CONNECT_MAX_BACKOFF);
             }
         }
     }
@@ -67,36 +177,32 @@ pub async fn start_socket_listener_on(
 
 /// CLI shim: connects to the GUI's Unix socket and proxies stdin/stdout ↔ socket.
 /// This makes the shim appear as a normal stdio MCP server to Claude Code.
-pub async fn run_mcp_shim() -> Result<(), Box<dyn std::error::Error>> {
-    let path = socket_path();
-
-    let stream = UnixStream::connect(&path).await.map_err(|e| {
-        format!(
-            "Failed to connect to PomodoroAI socket at {:?}: {}. Is the GUI running?",
-            path, e
-        )
-    })?;
-
-    let (mut sock_read, mut sock_write) = tokio::io::split(stream);
-    let mut stdin = tokio::io::stdin();
-    let mut stdout = tokio::io::stdout();
-
-    // Bidirectional pipe: stdin → socket, socket → stdout
-    tokio::select! {
-        result = tokio::io::copy(&mut stdin, &mut sock_write) => {
-            result?;
-        }
-        result = tokio::io::copy(&mut sock_read, &mut stdout) => {
-            result?;
-        }
+pub async fn run_mcp_shim(settings: &Settings) -> Result<(), Box<dyn std::error::Error>> {
+    let path = socket_path(settings);
+
+    let mut stream = connect_with_backoff(&path).await?;
+
+    if settings.mcp.require_auth {
+        let auth = SocketAuth::load_or_create(&auth::token_path(&path)).await?;
+        auth.client_handshake(&mut stream).await?;
     }
 
+    let codec = framing::negotiate_codec_client(&mut stream).await?;
+    let mut stream = framing::wrap_stream(stream, codec);
+    let mut stdio = tokio::io::join(tokio::io::stdin(), tokio::io::stdout());
+
+    // `copy_bidirectional` shuts down each direction independently on
+    // half-close, so a response that's still streaming out isn't truncated
+    // just because stdin (or the socket) closed first.
+    tokio::io::copy_bidirectional(&mut stdio, &mut stream).await?;
+
     Ok(())
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::mcp::framing::{self, Codec};
     use crate::timer::TimerEngine;
     use std::time::Duration;
     use tokio::io::{AsyncReadExt, AsyncWriteExt};
@@ -109,7 +215,7 @@ mod tests {
 
     #[test]
     fn test_socket_path_structure() {
-        let path = socket_path();
+        let path = socket_path(&Settings::default());
         assert!(path.ends_with("com.pomodoroai.app/pomodoro.sock"));
     }
 
@@ -121,7 +227,7 @@ mod tests {
 
         let listener_path = path.clone();
         let _listener = tokio::spawn(async move {
-            start_socket_listener_on(&listener_path, engine, state)
+            start_socket_listener_on(&listener_path, engine, state, WorkerManager::new(), None, Settings::default())
                 .await
                 .unwrap();
         });
@@ -145,7 +251,7 @@ mod tests {
 
         let listener_path = path.clone();
         let _listener = tokio::spawn(async move {
-            start_socket_listener_on(&listener_path, engine, state)
+            start_socket_listener_on(&listener_path, engine, state, WorkerManager::new(), None, Settings::default())
                 .await
                 .unwrap();
         });
@@ -154,7 +260,11 @@ mod tests {
 
         let mut stream = UnixStream::connect(&path).await.unwrap();
 
-        // Send MCP initialize request (JSON-RPC over newline-delimited transport)
+        // Codec capability handshake: the server offers zstd, so the client picks it.
+        let codec = framing::negotiate_codec_client(&mut stream).await.unwrap();
+        assert_eq!(codec, Codec::Zstd);
+
+        // Send MCP initialize request as one length-prefixed frame.
         let init_request = serde_json::json!({
             "jsonrpc": "2.0",
             "id": 1,
@@ -165,18 +275,16 @@ mod tests {
                 "clientInfo": { "name": "test", "version": "0.1.0" }
             }
         });
-        let mut msg = serde_json::to_vec(&init_request).unwrap();
-        msg.push(b'\n');
-        stream.write_all(&msg).await.unwrap();
+        let msg = serde_json::to_vec(&init_request).unwrap();
+        framing::write_frame(&mut stream, codec, &msg).await.unwrap();
 
-        // Read response
-        let mut buf = vec![0u8; 4096];
-        let n = tokio::time::timeout(Duration::from_secs(2), stream.read(&mut buf))
+        // Read the response frame.
+        let received = tokio::time::timeout(Duration::from_secs(2), framing::read_frame(&mut stream, codec))
             .await
             .expect("response timed out")
             .expect("read failed");
 
-        let response: serde_json::Value = serde_json::from_slice(&buf[..n]).unwrap();
+        let response: serde_json::Value = serde_json::from_slice(&received).unwrap();
         assert_eq!(response["jsonrpc"], "2.0");
         assert_eq!(response["id"], 1);
         // Server should return capabilities with tools enabled
@@ -193,7 +301,7 @@ mod tests {
 
         let listener_path = path.clone();
         let _listener = tokio::spawn(async move {
-            start_socket_listener_on(&listener_path, engine, state)
+            start_socket_listener_on(&listener_path, engine, state, WorkerManager::new(), None, Settings::default())
                 .await
                 .unwrap();
         });
@@ -222,7 +330,7 @@ mod tests {
 
         let listener_path = path.clone();
         let _listener = tokio::spawn(async move {
-            start_socket_listener_on(&listener_path, engine, state)
+            start_socket_listener_on(&listener_path, engine, state, WorkerManager::new(), None, Settings::default())
                 .await
                 .unwrap();
         });
@@ -243,4 +351,42 @@ mod tests {
         let result = UnixStream::connect(&bad_path).await;
         assert!(result.is_err(), "Should fail when socket doesn't exist");
     }
+
+    #[tokio::test]
+    async fn test_unauthenticated_client_is_rejected_when_auth_required() {
+        let path = temp_socket_path();
+        let engine = TimerEngine::new();
+        let state = Arc::new(StateManager::in_memory().unwrap());
+        let auth = Arc::new(
+            SocketAuth::load_or_create(&path.with_file_name("token"))
+                .await
+                .unwrap(),
+        );
+
+        let listener_path = path.clone();
+        let _listener = tokio::spawn(async move {
+            start_socket_listener_on(&listener_path, engine, state, WorkerManager::new(), Some(auth), Settings::default())
+                .await
+                .unwrap();
+        });
+
+        tokio::time::sleep(Duration::from_millis(50)).await;
+
+        let mut stream = UnixStream::connect(&path).await.unwrap();
+
+        // Reply with garbage instead of the expected HMAC digest.
+        let mut nonce_line = [0u8; 64];
+        let n = stream.read(&mut nonce_line).await.unwrap();
+        assert!(n > 0, "server should send a nonce line");
+        stream.write_all(b"not-the-right-digest\n").await.unwrap();
+
+        // The server should close the connection rather than serve MCP requests.
+        let mut buf = [0u8; 8];
+        let read = tokio::time::timeout(Duration::from_secs(2), stream.read(&mut buf))
+            .await
+            .expect("read should not hang");
+        assert_eq!(read.unwrap_or(0), 0, "connection should be closed after failed auth");
+
+        let _ = std::fs::remove_file(&path);
+    }
 }
@@ -0,0 +1,205 @@
+use config::{Config, ConfigError, Environment, File};
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+
+/// Database connection settings.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DatabaseSettings {
+    /// Filename of the SQLite database inside the app data dir.
+    pub path: String,
+    /// Run against an in-memory database instead (useful for demos/tests).
+    pub in_memory: bool,
+    /// Minimum idle connections the r2d2 pool keeps open.
+    pub min_connections: u32,
+    /// Maximum connections the r2d2 pool may open.
+    pub max_connections: u32,
+}
+
+impl Default for DatabaseSettings {
+    fn default() -> Self {
+        DatabaseSettings {
+            path: "pomodoro.db".into(),
+            in_memory: false,
+            min_connections: 1,
+            max_connections: 8,
+        }
+    }
+}
+
+/// Default durations used when a `start_timer` call omits them.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TimerSettings {
+    pub default_work_minutes: u32,
+    pub default_break_minutes: u32,
+    /// Long break duration used by `start_cycle` when the caller omits it.
+    pub default_long_break_minutes: u32,
+    /// Work sessions per set before a long break, used by `start_cycle` when
+    /// the caller omits it.
+    pub default_sessions_before_long_break: u32,
+}
+
+impl Default for TimerSettings {
+    fn default() -> Self {
+        TimerSettings {
+            default_work_minutes: 25,
+            default_break_minutes: 5,
+            default_long_break_minutes: 15,
+            default_sessions_before_long_break: 4,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DiagnosticsSettings {
+    /// Enable verbose tracing of workers and timer events.
+    pub tracing: bool,
+}
+
+impl Default for DiagnosticsSettings {
+    fn default() -> Self {
+        DiagnosticsSettings { tracing: false }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct McpSettings {
+    /// Socket filename inside the app's socket directory. Relative paths are
+    /// resolved against `<OS data dir>/com.pomodoroai.app`; absolute paths
+    /// are used as given.
+    pub socket_path: String,
+    /// Require the HMAC challenge-response handshake before serving a
+    /// connecting client. Disable only for local development.
+    pub require_auth: bool,
+}
+
+impl Default for McpSettings {
+    fn default() -> Self {
+        McpSettings {
+            socket_path: "pomodoro.sock".into(),
+            require_auth: true,
+        }
+    }
+}
+
+/// Settings for the optional LAN-reachable MCP transport. Disabled by
+/// default since it exposes timer control to the network rather than just
+/// the local machine.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TcpSettings {
+    pub enabled: bool,
+    /// Address the TCP listener binds, e.g. "0.0.0.0:7590".
+    pub bind_addr: String,
+    /// Instance name advertised over mDNS/DNS-SD alongside the service type.
+    pub mdns_service_name: String,
+}
+
+impl Default for TcpSettings {
+    fn default() -> Self {
+        TcpSettings {
+            enabled: false,
+            bind_addr: "0.0.0.0:7590".into(),
+            mdns_service_name: "PomodoroAI".into(),
+        }
+    }
+}
+
+/// Settings for the optional remote QUIC transport, the network-reachable
+/// counterpart to the Unix socket for agents on other machines.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct QuicSettings {
+    pub enabled: bool,
+    /// Address the QUIC endpoint binds, e.g. "0.0.0.0:7591".
+    pub bind_addr: String,
+}
+
+impl Default for QuicSettings {
+    fn default() -> Self {
+        QuicSettings {
+            enabled: false,
+            bind_addr: "0.0.0.0:7591".into(),
+        }
+    }
+}
+
+/// Resolved application configuration: a `Default` impl overlaid with
+/// `config.toml` (if present) and `POMODORO_*` environment variables.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct Settings {
+    pub database: DatabaseSettings,
+    pub timer: TimerSettings,
+    pub diagnostics: DiagnosticsSettings,
+    pub mcp: McpSettings,
+    pub tcp: TcpSettings,
+    pub quic: QuicSettings,
+}
+
+impl Settings {
+    /// Loads settings by starting from `Settings::default()`, then
+    /// overlaying `config_dir/config.toml` if it exists, then overlaying
+    /// `POMODORO_*` environment variables.
+    pub fn load(config_dir: &Path) -> Result<Settings, ConfigError> {
+        let config_path = config_dir.join("config.toml");
+
+        Config::builder()
+            .add_source(Config::try_from(&Settings::default())?)
+            .add_source(File::from(config_path).required(false))
+            .add_source(Environment::with_prefix("POMODORO").separator("__"))
+            .build()?
+            .try_deserialize()
+    }
+
+    /// Resolves `database.path` to an absolute path under `app_data_dir`
+    /// unless it is already absolute.
+    pub fn database_path(&self, app_data_dir: &Path) -> PathBuf {
+        let path = Path::new(&self.database.path);
+        if path.is_absolute() {
+            path.to_path_buf()
+        } else {
+            app_data_dir.join(path)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_defaults_without_config_file() {
+        let dir = std::env::temp_dir().join(format!("pomodoro-settings-test-{}", uuid::Uuid::new_v4()));
+        let settings = Settings::load(&dir).unwrap();
+        assert_eq!(settings.database.path, "pomodoro.db");
+        assert_eq!(settings.timer.default_work_minutes, 25);
+        assert!(!settings.diagnostics.tracing);
+    }
+
+    #[test]
+    fn test_file_overrides_defaults() {
+        let dir = std::env::temp_dir().join(format!("pomodoro-settings-test-{}", uuid::Uuid::new_v4()));
+        std::fs::create_dir_all(&dir).unwrap();
+        std::fs::write(
+            dir.join("config.toml"),
+            "[database]\npath = \"custom.db\"\nin_memory = true\n\n[timer]\ndefault_work_minutes = 50\ndefault_break_minutes = 10\n",
+        )
+        .unwrap();
+
+        let settings = Settings::load(&dir).unwrap();
+        assert_eq!(settings.database.path, "custom.db");
+        assert!(settings.database.in_memory);
+        assert_eq!(settings.timer.default_work_minutes, 50);
+        // Untouched sections keep their defaults.
+        assert!(!settings.diagnostics.tracing);
+
+        std::fs::remove_dir_all(&dir).ok();
+    }
+
+    #[test]
+    fn test_database_path_resolves_relative_to_app_data_dir() {
+        let settings = Settings::default();
+        let app_data_dir = Path::new("/tmp/pomodoro-app-data");
+        assert_eq!(
+            settings.database_path(app_data_dir),
+            app_data_dir.join("pomodoro.db")
+        );
+    }
+}
@@ -1,33 +1,59 @@
-use crate::timer::{Origin, Session, SessionStatus};
-use rusqlite::{params, Connection};
-use std::sync::Mutex;
+use crate::timer::{Origin, ScheduledSession, Session, SessionKind, SessionStatus};
+use r2d2::Pool;
+use r2d2_sqlite::SqliteConnectionManager;
+use rusqlite::params;
+use thiserror::Error;
+
+#[derive(Debug, Error)]
+pub enum StateError {
+    #[error("connection pool error: {0}")]
+    Pool(#[from] r2d2::Error),
+    #[error("database error: {0}")]
+    Sqlite(#[from] rusqlite::Error),
+}
 
 pub struct StateManager {
-    conn: Mutex<Connection>,
+    pool: Pool<SqliteConnectionManager>,
 }
 
 impl StateManager {
-    pub fn new(db_path: &str) -> Result<Self, rusqlite::Error> {
-        let conn = Connection::open(db_path)?;
-        let manager = StateManager {
-            conn: Mutex::new(conn),
-        };
+    pub fn new(db_path: &str) -> Result<Self, StateError> {
+        Self::with_pool_size(db_path, 1, 8)
+    }
+
+    /// Opens a pooled connection to the SQLite database at `db_path`, enabling
+    /// WAL mode on each pooled connection so readers don't block writers.
+    pub fn with_pool_size(db_path: &str, min_conn: u32, max_conn: u32) -> Result<Self, StateError> {
+        let manager = SqliteConnectionManager::file(db_path).with_init(|conn| {
+            conn.execute_batch("PRAGMA journal_mode=WAL; PRAGMA foreign_keys=ON;")?;
+            Ok(())
+        });
+        let pool = Pool::builder()
+            .min_idle(Some(min_conn))
+            .max_size(max_conn.max(min_conn).max(1))
+            .build(manager)?;
+        let manager = StateManager { pool };
         manager.run_migrations()?;
         Ok(manager)
     }
 
-    #[cfg(test)]
-    pub fn in_memory() -> Result<Self, rusqlite::Error> {
-        let conn = Connection::open_in_memory()?;
-        let manager = StateManager {
-            conn: Mutex::new(conn),
-        };
+    /// Opens an in-memory database. A single connection is kept alive for
+    /// the pool's lifetime since SQLite `:memory:` databases are private to
+    /// the connection that created them.
+    pub fn in_memory() -> Result<Self, StateError> {
+        let manager = SqliteConnectionManager::memory();
+        let pool = Pool::builder()
+            .max_size(1)
+            .max_lifetime(None)
+            .idle_timeout(None)
+            .build(manager)?;
+        let manager = StateManager { pool };
         manager.run_migrations()?;
         Ok(manager)
     }
 
-    fn run_migrations(&self) -> Result<(), rusqlite::Error> {
-        let conn = self.conn.lock().unwrap();
+    fn run_migrations(&self) -> Result<(), StateError> {
+        let conn = self.pool.get()?;
         conn.execute_batch(
             "CREATE TABLE IF NOT EXISTS sessions (
                 id TEXT PRIMARY KEY NOT NULL,
@@ -39,16 +65,42 @@ impl StateManager {
                 status TEXT NOT NULL CHECK(status IN ('Running', 'Completed', 'Stopped'))
             );
             CREATE INDEX IF NOT EXISTS idx_sessions_started_at ON sessions(started_at);
-            CREATE INDEX IF NOT EXISTS idx_sessions_status ON sessions(status);",
+            CREATE INDEX IF NOT EXISTS idx_sessions_status ON sessions(status);
+
+            CREATE TABLE IF NOT EXISTS pending_sessions (
+                id TEXT PRIMARY KEY NOT NULL,
+                label TEXT NOT NULL CHECK(length(label) >= 1 AND length(label) <= 64),
+                duration_secs INTEGER NOT NULL CHECK(duration_secs > 0),
+                origin TEXT NOT NULL CHECK(origin IN ('Human', 'Agent')),
+                run_at TEXT NOT NULL,
+                recurrence_secs INTEGER
+            );
+            CREATE INDEX IF NOT EXISTS idx_pending_sessions_run_at ON pending_sessions(run_at);",
         )?;
+
+        // `kind`/`cycle_index` were added after `sessions` first shipped, so
+        // `CREATE TABLE IF NOT EXISTS` above is a no-op against a database
+        // created by an earlier build - retrofit the columns explicitly for
+        // anyone upgrading from before they existed.
+        let has_kind_column = conn
+            .prepare("SELECT 1 FROM pragma_table_info('sessions') WHERE name = 'kind'")?
+            .exists([])?;
+        if !has_kind_column {
+            conn.execute_batch(
+                "ALTER TABLE sessions ADD COLUMN kind TEXT NOT NULL DEFAULT 'Work'
+                    CHECK(kind IN ('Work', 'ShortBreak', 'LongBreak'));
+                ALTER TABLE sessions ADD COLUMN cycle_index INTEGER NOT NULL DEFAULT 0;",
+            )?;
+        }
+
         Ok(())
     }
 
-    pub fn save_session(&self, session: &Session) -> Result<(), rusqlite::Error> {
-        let conn = self.conn.lock().unwrap();
+    pub fn save_session(&self, session: &Session) -> Result<(), StateError> {
+        let conn = self.pool.get()?;
         conn.execute(
-            "INSERT INTO sessions (id, label, duration_secs, started_at, ended_at, origin, status)
-             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)",
+            "INSERT INTO sessions (id, label, duration_secs, started_at, ended_at, origin, status, kind, cycle_index)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9)",
             params![
                 session.id,
                 session.label,
@@ -57,6 +109,8 @@ impl StateManager {
                 session.ended_at,
                 format!("{:?}", session.origin),
                 format!("{:?}", session.status),
+                format!("{:?}", session.kind),
+                session.cycle_index,
             ],
         )?;
         Ok(())
@@ -67,8 +121,8 @@ impl StateManager {
         id: &str,
         status: &SessionStatus,
         ended_at: &str,
-    ) -> Result<(), rusqlite::Error> {
-        let conn = self.conn.lock().unwrap();
+    ) -> Result<(), StateError> {
+        let conn = self.pool.get()?;
         conn.execute(
             "UPDATE sessions SET status = ?1, ended_at = ?2 WHERE id = ?3",
             params![format!("{:?}", status), ended_at, id],
@@ -80,9 +134,9 @@ impl StateManager {
         &self,
         start_date: Option<&str>,
         end_date: Option<&str>,
-    ) -> Result<Vec<Session>, rusqlite::Error> {
-        let conn = self.conn.lock().unwrap();
-        let mut query = "SELECT id, label, duration_secs, started_at, ended_at, origin, status FROM sessions WHERE 1=1".to_string();
+    ) -> Result<Vec<Session>, StateError> {
+        let conn = self.pool.get()?;
+        let mut query = "SELECT id, label, duration_secs, started_at, ended_at, origin, status, kind, cycle_index FROM sessions WHERE 1=1".to_string();
         let mut param_values: Vec<String> = Vec::new();
 
         if let Some(start) = start_date {
@@ -101,37 +155,121 @@ impl StateManager {
             .map(|v| v as &dyn rusqlite::types::ToSql)
             .collect();
 
-        let sessions = stmt.query_map(params.as_slice(), |row| {
-            let origin_str: String = row.get(5)?;
-            let status_str: String = row.get(6)?;
-            Ok(Session {
+        let sessions = stmt.query_map(params.as_slice(), Self::row_to_session)?;
+
+        Ok(sessions.collect::<Result<Vec<_>, _>>()?)
+    }
+
+    /// The most recently started session, used to resume an automatic cycle's
+    /// position after a restart.
+    pub fn get_last_session(&self) -> Result<Option<Session>, StateError> {
+        let conn = self.pool.get()?;
+        conn.query_row(
+            "SELECT id, label, duration_secs, started_at, ended_at, origin, status, kind, cycle_index
+             FROM sessions ORDER BY started_at DESC LIMIT 1",
+            [],
+            Self::row_to_session,
+        )
+        .map(Some)
+        .or_else(|e| match e {
+            rusqlite::Error::QueryReturnedNoRows => Ok(None),
+            e => Err(e.into()),
+        })
+    }
+
+    fn row_to_session(row: &rusqlite::Row) -> rusqlite::Result<Session> {
+        let origin_str: String = row.get(5)?;
+        let status_str: String = row.get(6)?;
+        let kind_str: String = row.get(7)?;
+        Ok(Session {
+            id: row.get(0)?,
+            label: row.get(1)?,
+            duration_secs: row.get(2)?,
+            started_at: row.get(3)?,
+            ended_at: row.get(4)?,
+            origin: match origin_str.as_str() {
+                "Agent" => Origin::Agent,
+                _ => Origin::Human,
+            },
+            status: match status_str.as_str() {
+                "Completed" => SessionStatus::Completed,
+                "Stopped" => SessionStatus::Stopped,
+                _ => SessionStatus::Running,
+            },
+            kind: match kind_str.as_str() {
+                "ShortBreak" => SessionKind::ShortBreak,
+                "LongBreak" => SessionKind::LongBreak,
+                _ => SessionKind::Work,
+            },
+            cycle_index: row.get(8)?,
+        })
+    }
+
+    pub fn cleanup_stale_running(&self) -> Result<usize, StateError> {
+        let conn = self.pool.get()?;
+        let count = conn.execute(
+            "UPDATE sessions SET status = 'Stopped', ended_at = datetime('now') WHERE status = 'Running'",
+            [],
+        )?;
+        Ok(count)
+    }
+
+    pub fn save_scheduled_session(&self, sched: &ScheduledSession) -> Result<(), StateError> {
+        let conn = self.pool.get()?;
+        conn.execute(
+            "INSERT INTO pending_sessions (id, label, duration_secs, origin, run_at, recurrence_secs)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
+            params![
+                sched.id,
+                sched.label,
+                sched.duration_secs,
+                format!("{:?}", sched.origin),
+                sched.run_at,
+                sched.recurrence_secs,
+            ],
+        )?;
+        Ok(())
+    }
+
+    /// Scheduled sessions whose `run_at` has passed, ready for the scheduler
+    /// worker to fire.
+    pub fn due_scheduled_sessions(&self, now: &str) -> Result<Vec<ScheduledSession>, StateError> {
+        let conn = self.pool.get()?;
+        let mut stmt = conn.prepare(
+            "SELECT id, label, duration_secs, origin, run_at, recurrence_secs
+             FROM pending_sessions WHERE julianday(run_at) <= julianday(?1)",
+        )?;
+        let sessions = stmt.query_map(params![now], |row| {
+            let origin_str: String = row.get(3)?;
+            Ok(ScheduledSession {
                 id: row.get(0)?,
                 label: row.get(1)?,
                 duration_secs: row.get(2)?,
-                started_at: row.get(3)?,
-                ended_at: row.get(4)?,
                 origin: match origin_str.as_str() {
                     "Agent" => Origin::Agent,
                     _ => Origin::Human,
                 },
-                status: match status_str.as_str() {
-                    "Completed" => SessionStatus::Completed,
-                    "Stopped" => SessionStatus::Stopped,
-                    _ => SessionStatus::Running,
-                },
+                run_at: row.get(4)?,
+                recurrence_secs: row.get(5)?,
             })
         })?;
-
-        sessions.collect()
+        Ok(sessions.collect::<Result<Vec<_>, _>>()?)
     }
 
-    pub fn cleanup_stale_running(&self) -> Result<usize, rusqlite::Error> {
-        let conn = self.conn.lock().unwrap();
-        let count = conn.execute(
-            "UPDATE sessions SET status = 'Stopped', ended_at = datetime('now') WHERE status = 'Running'",
-            [],
+    /// Pushes `run_at` forward for a recurring scheduled session.
+    pub fn reschedule_scheduled_session(&self, id: &str, next_run_at: &str) -> Result<(), StateError> {
+        let conn = self.pool.get()?;
+        conn.execute(
+            "UPDATE pending_sessions SET run_at = ?1 WHERE id = ?2",
+            params![next_run_at, id],
         )?;
-        Ok(count)
+        Ok(())
+    }
+
+    pub fn delete_scheduled_session(&self, id: &str) -> Result<(), StateError> {
+        let conn = self.pool.get()?;
+        conn.execute("DELETE FROM pending_sessions WHERE id = ?1", params![id])?;
+        Ok(())
     }
 }
 
@@ -148,6 +286,8 @@ mod tests {
             ended_at: None,
             origin,
             status,
+            kind: SessionKind::Work,
+            cycle_index: 0,
         }
     }
 
@@ -226,4 +366,105 @@ mod tests {
         let history = mgr.get_history(None, None).unwrap();
         assert!(history.is_empty());
     }
+
+    fn make_scheduled(id: &str, run_at: &str, recurrence_secs: Option<u64>) -> ScheduledSession {
+        ScheduledSession {
+            id: id.to_string(),
+            label: "Scheduled".to_string(),
+            duration_secs: 1500,
+            origin: Origin::Human,
+            run_at: run_at.to_string(),
+            recurrence_secs,
+        }
+    }
+
+    #[test]
+    fn test_due_scheduled_sessions_filters_by_run_at() {
+        let mgr = StateManager::in_memory().unwrap();
+        mgr.save_scheduled_session(&make_scheduled("s1", "2024-01-01T09:00:00Z", None))
+            .unwrap();
+        mgr.save_scheduled_session(&make_scheduled("s2", "2024-01-03T09:00:00Z", None))
+            .unwrap();
+
+        let due = mgr.due_scheduled_sessions("2024-01-02T00:00:00Z").unwrap();
+        assert_eq!(due.len(), 1);
+        assert_eq!(due[0].id, "s1");
+    }
+
+    #[test]
+    fn test_reschedule_scheduled_session_updates_run_at() {
+        let mgr = StateManager::in_memory().unwrap();
+        mgr.save_scheduled_session(&make_scheduled("s1", "2024-01-01T09:00:00Z", Some(86400)))
+            .unwrap();
+
+        mgr.reschedule_scheduled_session("s1", "2024-01-02T09:00:00Z")
+            .unwrap();
+
+        let due = mgr.due_scheduled_sessions("2024-01-01T09:00:01Z").unwrap();
+        assert!(due.is_empty());
+        let due = mgr.due_scheduled_sessions("2024-01-02T09:00:00Z").unwrap();
+        assert_eq!(due.len(), 1);
+    }
+
+    #[test]
+    fn test_delete_scheduled_session_removes_it() {
+        let mgr = StateManager::in_memory().unwrap();
+        mgr.save_scheduled_session(&make_scheduled("s1", "2024-01-01T09:00:00Z", None))
+            .unwrap();
+        mgr.delete_scheduled_session("s1").unwrap();
+
+        let due = mgr.due_scheduled_sessions("2024-01-02T00:00:00Z").unwrap();
+        assert!(due.is_empty());
+    }
+
+    #[test]
+    fn test_pooled_file_database_saves_and_reads() {
+        let db_path = std::env::temp_dir().join(format!("pomodoro-pool-test-{}.db", uuid::Uuid::new_v4()));
+        let mgr = StateManager::with_pool_size(db_path.to_str().unwrap(), 2, 4).unwrap();
+        let session = make_session("s1", "Pooled", Origin::Human, SessionStatus::Running);
+        mgr.save_session(&session).unwrap();
+
+        let history = mgr.get_history(None, None).unwrap();
+        assert_eq!(history.len(), 1);
+
+        std::fs::remove_file(&db_path).ok();
+        std::fs::remove_file(format!("{}-wal", db_path.display())).ok();
+        std::fs::remove_file(format!("{}-shm", db_path.display())).ok();
+    }
+
+    /// Simulates a database created before `kind`/`cycle_index` existed:
+    /// `run_migrations` should retrofit the columns instead of leaving the
+    /// pre-existing table untouched.
+    #[test]
+    fn test_migrates_sessions_table_missing_kind_and_cycle_index() {
+        let db_path = std::env::temp_dir().join(format!("pomodoro-migrate-test-{}.db", uuid::Uuid::new_v4()));
+        {
+            let conn = rusqlite::Connection::open(&db_path).unwrap();
+            conn.execute_batch(
+                "CREATE TABLE sessions (
+                    id TEXT PRIMARY KEY NOT NULL,
+                    label TEXT NOT NULL,
+                    duration_secs INTEGER NOT NULL,
+                    started_at TEXT NOT NULL,
+                    ended_at TEXT,
+                    origin TEXT NOT NULL,
+                    status TEXT NOT NULL
+                );",
+            )
+            .unwrap();
+        }
+
+        let mgr = StateManager::with_pool_size(db_path.to_str().unwrap(), 1, 1).unwrap();
+        let session = make_session("s1", "Upgraded", Origin::Human, SessionStatus::Running);
+        mgr.save_session(&session).unwrap();
+
+        let history = mgr.get_history(None, None).unwrap();
+        assert_eq!(history.len(), 1);
+        assert_eq!(history[0].kind, SessionKind::Work);
+        assert_eq!(history[0].cycle_index, 0);
+
+        std::fs::remove_file(&db_path).ok();
+        std::fs::remove_file(format!("{}-wal", db_path.display())).ok();
+        std::fs::remove_file(format!("{}-shm", db_path.display())).ok();
+    }
 }
@@ -1,8 +1,10 @@
+use async_stream::stream;
 use chrono::Utc;
+use futures_core::Stream;
 use serde::{Deserialize, Serialize};
 use std::sync::Arc;
 use thiserror::Error;
-use tokio::sync::{broadcast, Mutex};
+use tokio::sync::{broadcast, mpsc, Mutex};
 use uuid::Uuid;
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
@@ -37,6 +39,25 @@ impl std::fmt::Display for SessionStatus {
     }
 }
 
+/// Where a session falls in the Pomodoro technique: a focus block, a short
+/// break between work sessions, or a long break after a full set of them.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq)]
+pub enum SessionKind {
+    Work,
+    ShortBreak,
+    LongBreak,
+}
+
+impl std::fmt::Display for SessionKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SessionKind::Work => write!(f, "work"),
+            SessionKind::ShortBreak => write!(f, "short_break"),
+            SessionKind::LongBreak => write!(f, "long_break"),
+        }
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Session {
     pub id: String,
@@ -46,6 +67,45 @@ pub struct Session {
     pub ended_at: Option<String>,
     pub origin: Origin,
     pub status: SessionStatus,
+    pub kind: SessionKind,
+    /// How many work sessions have completed in the current set (resets
+    /// after a long break). Always 0 for one-shot, non-cycling sessions.
+    pub cycle_index: u32,
+}
+
+/// Durations and set size for an automatic Pomodoro cycle: work sessions
+/// loop into short breaks, and every `sessions_before_long_break`th work
+/// session is followed by a long break instead, looping until cancelled.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+pub struct CycleConfig {
+    pub work_minutes: u32,
+    pub short_break_minutes: u32,
+    pub long_break_minutes: u32,
+    pub sessions_before_long_break: u32,
+}
+
+/// A session to start at a future time, optionally repeating on a fixed
+/// interval. Persisted so a `start_timer` scheduled for later survives a
+/// restart of the app; picked up by the background scheduler worker once
+/// `run_at` is reached.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ScheduledSession {
+    pub id: String,
+    pub label: String,
+    pub duration_secs: u64,
+    pub origin: Origin,
+    /// RFC 3339 timestamp of the next (or only) run.
+    pub run_at: String,
+    /// Interval to reschedule by after firing, or `None` for a one-shot.
+    pub recurrence_secs: Option<u64>,
+}
+
+/// Parses a human-friendly duration string (e.g. `"25m"`, `"1h30m"`,
+/// `"90s"`) into whole seconds.
+pub fn parse_duration_secs(input: &str) -> Result<u64, TimerError> {
+    humantime::parse_duration(input)
+        .map(|d| d.as_secs())
+        .map_err(|_| TimerError::InvalidDuration)
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -64,6 +124,13 @@ pub enum TimerEvent {
     Stopped {
         session: Session,
     },
+    /// Synthetic event injected by `event_stream` when a subscriber falls
+    /// behind the broadcast channel's buffer: carries the authoritative
+    /// current status so the subscriber can resync instead of silently
+    /// missing the ticks it lagged past.
+    Resync {
+        status: TimerStatus,
+    },
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -71,6 +138,7 @@ pub struct TimerStatus {
     pub session: Option<Session>,
     pub remaining_secs: u64,
     pub is_running: bool,
+    pub is_paused: bool,
 }
 
 #[derive(Debug, Error)]
@@ -79,12 +147,32 @@ pub enum TimerError {
     AlreadyRunning,
     #[error("No timer is running")]
     NotRunning,
+    #[error("The timer is already paused")]
+    AlreadyPaused,
+    #[error("The timer is not paused")]
+    NotPaused,
     #[error("Invalid label: {0}")]
     InvalidLabel(String),
-    #[error("Invalid duration: must be between 1 and 1440 minutes")]
+    #[error("Invalid duration: must be between 1 minute and 1440 minutes")]
     InvalidDuration,
 }
 
+/// Commands sent from `TimerEngine` control methods to the running tick loop.
+enum EngineCommand {
+    Pause,
+    Resume,
+    Cancel,
+}
+
+/// Tracks progress through an in-flight automatic cycle so the tick loop can
+/// chain the next session when the current one completes.
+struct CycleState {
+    config: CycleConfig,
+    label: String,
+    origin: Origin,
+    cycle_index: u32,
+}
+
 impl Serialize for TimerError {
     fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
     where
@@ -97,7 +185,9 @@ impl Serialize for TimerError {
 struct TimerInner {
     session: Option<Session>,
     remaining_secs: u64,
-    cancel_tx: Option<tokio::sync::oneshot::Sender<()>>,
+    paused: bool,
+    control_tx: Option<mpsc::Sender<EngineCommand>>,
+    cycle: Option<CycleState>,
 }
 
 #[derive(Clone)]
@@ -119,7 +209,9 @@ impl TimerEngine {
             inner: Arc::new(Mutex::new(TimerInner {
                 session: None,
                 remaining_secs: 0,
-                cancel_tx: None,
+                paused: false,
+                control_tx: None,
+                cycle: None,
             })),
             event_tx,
         }
@@ -129,11 +221,33 @@ impl TimerEngine {
         self.event_tx.subscribe()
     }
 
+    /// A lag-tolerant alternative to `subscribe`: wraps the broadcast
+    /// receiver in a `Stream` that never terminates on `RecvError::Lagged`.
+    /// A slow consumer (a stalled GUI, a backed-up SSE feed) instead gets a
+    /// synthetic `TimerEvent::Resync` built from `get_status()`, so it
+    /// recovers the authoritative current state rather than missing the
+    /// ticks it fell behind on.
+    pub fn event_stream(&self) -> impl Stream<Item = TimerEvent> + Send + 'static {
+        let mut rx = self.subscribe();
+        let engine = self.clone();
+        stream! {
+            loop {
+                match rx.recv().await {
+                    Ok(event) => yield event,
+                    Err(broadcast::error::RecvError::Lagged(_)) => {
+                        yield TimerEvent::Resync { status: engine.get_status().await };
+                    }
+                    Err(broadcast::error::RecvError::Closed) => break,
+                }
+            }
+        }
+    }
+
     pub fn sender(&self) -> broadcast::Sender<TimerEvent> {
         self.event_tx.clone()
     }
 
-    fn validate_label(label: &str) -> Result<String, TimerError> {
+    pub(crate) fn validate_label(label: &str) -> Result<String, TimerError> {
         let trimmed = label.trim().to_string();
         if trimmed.is_empty() {
             return Err(TimerError::InvalidLabel("label cannot be empty".into()));
@@ -152,10 +266,14 @@ impl TimerEngine {
     }
 
     fn validate_duration(minutes: u32) -> Result<u64, TimerError> {
-        if !(1..=1440).contains(&minutes) {
+        Self::validate_duration_secs(minutes as u64 * 60)
+    }
+
+    pub(crate) fn validate_duration_secs(duration_secs: u64) -> Result<u64, TimerError> {
+        if !(60..=1440 * 60).contains(&duration_secs) {
             return Err(TimerError::InvalidDuration);
         }
-        Ok(minutes as u64 * 60)
+        Ok(duration_secs)
     }
 
     pub async fn start(
@@ -163,15 +281,75 @@ impl TimerEngine {
         duration_minutes: u32,
         label: &str,
         origin: Origin,
+    ) -> Result<Session, TimerError> {
+        self.start_for_secs(duration_minutes as u64 * 60, label, origin)
+            .await
+    }
+
+    /// Like `start`, but takes an exact duration in seconds rather than
+    /// whole minutes - used by `start_timer` callers that parsed a
+    /// human-friendly duration string (e.g. `"90s"`) with `parse_duration_secs`.
+    pub async fn start_for_secs(
+        &self,
+        duration_secs: u64,
+        label: &str,
+        origin: Origin,
     ) -> Result<Session, TimerError> {
         let label = Self::validate_label(label)?;
-        let duration_secs = Self::validate_duration(duration_minutes)?;
+        let duration_secs = Self::validate_duration_secs(duration_secs)?;
 
         let mut inner = self.inner.lock().await;
         if inner.session.is_some() {
             return Err(TimerError::AlreadyRunning);
         }
 
+        self.begin_session(&mut inner, duration_secs, label, origin, SessionKind::Work, 0)
+    }
+
+    /// Starts an automatically cycling Pomodoro set: work sessions loop into
+    /// short breaks, with a long break every `config.sessions_before_long_break`
+    /// work sessions, looping until `stop()` is called. `resume_cycle_index`
+    /// lets a caller continue a set that was in progress before a restart
+    /// (derived from the last persisted session's `cycle_index`).
+    pub async fn start_cycle(
+        &self,
+        config: CycleConfig,
+        label: &str,
+        origin: Origin,
+        resume_cycle_index: u32,
+    ) -> Result<Session, TimerError> {
+        let label = Self::validate_label(label)?;
+        let duration_secs = Self::validate_duration(config.work_minutes)?;
+        Self::validate_duration(config.short_break_minutes)?;
+        Self::validate_duration(config.long_break_minutes)?;
+
+        let mut inner = self.inner.lock().await;
+        if inner.session.is_some() {
+            return Err(TimerError::AlreadyRunning);
+        }
+
+        let cycle_index = resume_cycle_index % config.sessions_before_long_break.max(1);
+        inner.cycle = Some(CycleState {
+            config,
+            label: label.clone(),
+            origin: origin.clone(),
+            cycle_index,
+        });
+
+        self.begin_session(&mut inner, duration_secs, label, origin, SessionKind::Work, cycle_index)
+    }
+
+    /// Shared by `start`/`start_cycle`/cycle continuation: records the new
+    /// session, fires `Started`, and spawns its tick loop.
+    fn begin_session(
+        &self,
+        inner: &mut TimerInner,
+        duration_secs: u64,
+        label: String,
+        origin: Origin,
+        kind: SessionKind,
+        cycle_index: u32,
+    ) -> Result<Session, TimerError> {
         let session = Session {
             id: Uuid::new_v4().to_string(),
             label,
@@ -180,28 +358,30 @@ impl TimerEngine {
             ended_at: None,
             origin,
             status: SessionStatus::Running,
+            kind,
+            cycle_index,
         };
 
         inner.session = Some(session.clone());
         inner.remaining_secs = duration_secs;
+        inner.paused = false;
 
-        let (cancel_tx, cancel_rx) = tokio::sync::oneshot::channel();
-        inner.cancel_tx = Some(cancel_tx);
+        let (control_tx, control_rx) = mpsc::channel(8);
+        inner.control_tx = Some(control_tx);
 
         let _ = self.event_tx.send(TimerEvent::Started {
             session: session.clone(),
         });
 
-        // Spawn the tick loop
         let engine = self.clone();
         tokio::spawn(async move {
-            engine.tick_loop(cancel_rx).await;
+            engine.tick_loop(control_rx).await;
         });
 
         Ok(session)
     }
 
-    async fn tick_loop(&self, mut cancel_rx: tokio::sync::oneshot::Receiver<()>) {
+    async fn tick_loop(&self, mut control_rx: mpsc::Receiver<EngineCommand>) {
         let mut interval = tokio::time::interval(tokio::time::Duration::from_secs(1));
         interval.tick().await; // consume the immediate first tick
 
@@ -209,6 +389,9 @@ impl TimerEngine {
             tokio::select! {
                 _ = interval.tick() => {
                     let mut inner = self.inner.lock().await;
+                    if inner.paused {
+                        continue;
+                    }
                     if inner.remaining_secs == 0 {
                         break;
                     }
@@ -219,17 +402,7 @@ impl TimerEngine {
 
                     if let Some(session) = session {
                         if remaining == 0 {
-                            // Timer completed
-                            let mut inner = self.inner.lock().await;
-                            if let Some(ref mut s) = inner.session {
-                                s.status = SessionStatus::Completed;
-                                s.ended_at = Some(Utc::now().to_rfc3339());
-                                let _ = self.event_tx.send(TimerEvent::Completed {
-                                    session: s.clone(),
-                                });
-                            }
-                            inner.session = None;
-                            inner.cancel_tx = None;
+                            self.complete_current(session).await;
                             break;
                         } else {
                             let _ = self.event_tx.send(TimerEvent::Tick {
@@ -239,13 +412,107 @@ impl TimerEngine {
                         }
                     }
                 }
-                _ = &mut cancel_rx => {
-                    break;
+                cmd = control_rx.recv() => {
+                    match cmd {
+                        Some(EngineCommand::Pause) => {
+                            self.inner.lock().await.paused = true;
+                        }
+                        Some(EngineCommand::Resume) => {
+                            self.inner.lock().await.paused = false;
+                        }
+                        Some(EngineCommand::Cancel) | None => break,
+                    }
                 }
             }
         }
     }
 
+    /// Marks `session` completed, fires the event, and - if an automatic
+    /// cycle is in progress - chains the next work/break session.
+    async fn complete_current(&self, mut session: Session) {
+        session.status = SessionStatus::Completed;
+        session.ended_at = Some(Utc::now().to_rfc3339());
+        let _ = self.event_tx.send(TimerEvent::Completed {
+            session: session.clone(),
+        });
+
+        let mut inner = self.inner.lock().await;
+        inner.session = None;
+        inner.control_tx = None;
+        inner.remaining_secs = 0;
+        inner.paused = false;
+
+        let next = inner.cycle.as_mut().map(|cycle| {
+            let (next_kind, cycle_index) = match session.kind {
+                SessionKind::Work => {
+                    let index = cycle.cycle_index + 1;
+                    if index >= cycle.config.sessions_before_long_break {
+                        (SessionKind::LongBreak, 0)
+                    } else {
+                        (SessionKind::ShortBreak, index)
+                    }
+                }
+                SessionKind::ShortBreak | SessionKind::LongBreak => {
+                    (SessionKind::Work, cycle.cycle_index)
+                }
+            };
+            cycle.cycle_index = cycle_index;
+
+            let duration_minutes = match next_kind {
+                SessionKind::Work => cycle.config.work_minutes,
+                SessionKind::ShortBreak => cycle.config.short_break_minutes,
+                SessionKind::LongBreak => cycle.config.long_break_minutes,
+            };
+
+            (next_kind, duration_minutes, cycle.label.clone(), cycle.origin.clone(), cycle_index)
+        });
+
+        if let Some((kind, duration_minutes, label, origin, cycle_index)) = next {
+            // Cycle-managed durations were already validated when the cycle
+            // started and the lock is held since `session` was cleared, so
+            // neither call here fails in practice; the result is ignored.
+            if let Ok(duration_secs) = Self::validate_duration(duration_minutes) {
+                let _ = self.begin_session(&mut inner, duration_secs, label, origin, kind, cycle_index);
+            }
+        }
+    }
+
+    pub async fn pause(&self) -> Result<(), TimerError> {
+        let mut inner = self.inner.lock().await;
+        if inner.session.is_none() {
+            return Err(TimerError::NotRunning);
+        }
+        if inner.paused {
+            return Err(TimerError::AlreadyPaused);
+        }
+        inner.paused = true;
+        let control_tx = inner.control_tx.clone();
+        drop(inner);
+
+        if let Some(control_tx) = control_tx {
+            let _ = control_tx.send(EngineCommand::Pause).await;
+        }
+        Ok(())
+    }
+
+    pub async fn resume(&self) -> Result<(), TimerError> {
+        let mut inner = self.inner.lock().await;
+        if inner.session.is_none() {
+            return Err(TimerError::NotRunning);
+        }
+        if !inner.paused {
+            return Err(TimerError::NotPaused);
+        }
+        inner.paused = false;
+        let control_tx = inner.control_tx.clone();
+        drop(inner);
+
+        if let Some(control_tx) = control_tx {
+            let _ = control_tx.send(EngineCommand::Resume).await;
+        }
+        Ok(())
+    }
+
     pub async fn stop(&self) -> Result<Session, TimerError> {
         let mut inner = self.inner.lock().await;
         match inner.session.take() {
@@ -253,15 +520,22 @@ impl TimerEngine {
                 session.status = SessionStatus::Stopped;
                 session.ended_at = Some(Utc::now().to_rfc3339());
 
-                if let Some(cancel_tx) = inner.cancel_tx.take() {
-                    let _ = cancel_tx.send(());
+                // Stopping explicitly ends the cycle too, so completion of
+                // this (now-cancelled) tick loop won't chain another session.
+                inner.cycle = None;
+                inner.paused = false;
+                inner.remaining_secs = 0;
+                let control_tx = inner.control_tx.take();
+                drop(inner);
+
+                if let Some(control_tx) = control_tx {
+                    let _ = control_tx.send(EngineCommand::Cancel).await;
                 }
 
                 let _ = self.event_tx.send(TimerEvent::Stopped {
                     session: session.clone(),
                 });
 
-                inner.remaining_secs = 0;
                 Ok(session)
             }
             None => Err(TimerError::NotRunning),
@@ -274,6 +548,7 @@ impl TimerEngine {
             session: inner.session.clone(),
             remaining_secs: inner.remaining_secs,
             is_running: inner.session.is_some(),
+            is_paused: inner.paused,
         }
     }
 }
@@ -405,6 +680,48 @@ mod tests {
         assert!(matches!(event, TimerEvent::Started { .. }));
     }
 
+    #[tokio::test]
+    async fn test_event_stream_yields_events_normally() {
+        use futures_util::StreamExt;
+
+        let engine = TimerEngine::new();
+        let mut events = Box::pin(engine.event_stream());
+        engine.start(25, "Work", Origin::Human).await.unwrap();
+
+        let event = events.next().await.unwrap();
+        assert!(matches!(event, TimerEvent::Started { .. }));
+    }
+
+    #[tokio::test]
+    async fn test_event_stream_resyncs_instead_of_terminating_on_lag() {
+        use futures_util::StreamExt;
+
+        let engine = TimerEngine::new();
+        let mut events = Box::pin(engine.event_stream());
+
+        // The broadcast channel holds 64 events; start and stop a session
+        // enough times to blow past that before the stream is ever polled,
+        // forcing a `Lagged` error on the next read.
+        for _ in 0..80 {
+            engine.start(25, "Work", Origin::Human).await.unwrap();
+            engine.stop().await.unwrap();
+        }
+
+        let event = tokio::time::timeout(Duration::from_secs(1), events.next())
+            .await
+            .expect("stream should not hang")
+            .expect("stream should not terminate on lag");
+        assert!(matches!(event, TimerEvent::Resync { .. }));
+
+        // The stream keeps working afterward.
+        engine.start(25, "Work", Origin::Human).await.unwrap();
+        let next = tokio::time::timeout(Duration::from_secs(1), events.next())
+            .await
+            .expect("stream should not hang")
+            .expect("stream should not terminate");
+        assert!(matches!(next, TimerEvent::Started { .. }));
+    }
+
     #[tokio::test]
     async fn test_events_on_stop() {
         let engine = TimerEngine::new();
@@ -463,4 +780,167 @@ mod tests {
         let session = engine.start(25, "Work", Origin::Human).await.unwrap();
         assert!(Uuid::parse_str(&session.id).is_ok());
     }
+
+    #[tokio::test]
+    async fn test_one_shot_session_is_work_with_zero_cycle_index() {
+        let engine = TimerEngine::new();
+        let session = engine.start(25, "Work", Origin::Human).await.unwrap();
+        assert_eq!(session.kind, SessionKind::Work);
+        assert_eq!(session.cycle_index, 0);
+    }
+
+    fn test_cycle_config() -> CycleConfig {
+        CycleConfig {
+            work_minutes: 25,
+            short_break_minutes: 5,
+            long_break_minutes: 15,
+            sessions_before_long_break: 2,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_pause_and_resume() {
+        let engine = TimerEngine::new();
+        engine.start(25, "Work", Origin::Human).await.unwrap();
+
+        engine.pause().await.unwrap();
+        assert!(engine.get_status().await.is_paused);
+
+        engine.resume().await.unwrap();
+        assert!(!engine.get_status().await.is_paused);
+    }
+
+    #[tokio::test]
+    async fn test_pause_when_not_running() {
+        let engine = TimerEngine::new();
+        let result = engine.pause().await;
+        assert!(matches!(result.unwrap_err(), TimerError::NotRunning));
+    }
+
+    #[tokio::test]
+    async fn test_double_pause() {
+        let engine = TimerEngine::new();
+        engine.start(25, "Work", Origin::Human).await.unwrap();
+        engine.pause().await.unwrap();
+        let result = engine.pause().await;
+        assert!(matches!(result.unwrap_err(), TimerError::AlreadyPaused));
+    }
+
+    #[tokio::test]
+    async fn test_resume_when_not_paused() {
+        let engine = TimerEngine::new();
+        engine.start(25, "Work", Origin::Human).await.unwrap();
+        let result = engine.resume().await;
+        assert!(matches!(result.unwrap_err(), TimerError::NotPaused));
+    }
+
+    #[tokio::test]
+    async fn test_pause_preserves_elapsed_time() {
+        time::pause();
+        let engine = TimerEngine::new();
+        engine.start(1, "Work", Origin::Human).await.unwrap();
+
+        time::advance(Duration::from_secs(10)).await;
+        tokio::task::yield_now().await;
+        engine.pause().await.unwrap();
+        let paused_remaining = engine.get_status().await.remaining_secs;
+
+        time::advance(Duration::from_secs(20)).await;
+        tokio::task::yield_now().await;
+        let still_remaining = engine.get_status().await.remaining_secs;
+
+        assert_eq!(paused_remaining, still_remaining);
+    }
+
+    #[tokio::test]
+    async fn test_cycle_chains_work_into_short_break() {
+        time::pause();
+        let engine = TimerEngine::new();
+        let mut rx = engine.subscribe();
+        engine
+            .start_cycle(test_cycle_config(), "Focus", Origin::Human, 0)
+            .await
+            .unwrap();
+        let _ = rx.recv().await.unwrap(); // Started (work)
+
+        time::advance(Duration::from_secs(25 * 60)).await;
+        tokio::task::yield_now().await;
+
+        // Drain events until the work session's Completed event.
+        loop {
+            match rx.recv().await.unwrap() {
+                TimerEvent::Completed { session } => {
+                    assert_eq!(session.kind, SessionKind::Work);
+                    break;
+                }
+                _ => continue,
+            }
+        }
+
+        // The next Started event should be the chained short break.
+        let next = rx.recv().await.unwrap();
+        match next {
+            TimerEvent::Started { session } => {
+                assert_eq!(session.kind, SessionKind::ShortBreak);
+                assert_eq!(session.duration_secs, 5 * 60);
+            }
+            other => panic!("expected Started short break, got {:?}", other),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_cycle_resumes_from_given_index() {
+        let engine = TimerEngine::new();
+        let session = engine
+            .start_cycle(test_cycle_config(), "Focus", Origin::Human, 1)
+            .await
+            .unwrap();
+        assert_eq!(session.cycle_index, 1);
+    }
+
+    #[test]
+    fn test_parse_duration_secs_variants() {
+        assert_eq!(parse_duration_secs("90s").unwrap(), 90);
+        assert_eq!(parse_duration_secs("25m").unwrap(), 25 * 60);
+        assert_eq!(parse_duration_secs("1h30m").unwrap(), 90 * 60);
+    }
+
+    #[test]
+    fn test_parse_duration_secs_rejects_garbage() {
+        assert!(parse_duration_secs("not a duration").is_err());
+    }
+
+    #[tokio::test]
+    async fn test_start_for_secs_accepts_non_minute_aligned_duration() {
+        let engine = TimerEngine::new();
+        let session = engine
+            .start_for_secs(90, "Quick", Origin::Human)
+            .await
+            .unwrap();
+        assert_eq!(session.duration_secs, 90);
+    }
+
+    #[tokio::test]
+    async fn test_start_for_secs_rejects_too_short() {
+        let engine = TimerEngine::new();
+        let result = engine.start_for_secs(10, "Too short", Origin::Human).await;
+        assert!(matches!(result.unwrap_err(), TimerError::InvalidDuration));
+    }
+
+    #[tokio::test]
+    async fn test_stop_clears_cycle_so_it_does_not_resume() {
+        let engine = TimerEngine::new();
+        engine
+            .start_cycle(test_cycle_config(), "Focus", Origin::Human, 0)
+            .await
+            .unwrap();
+        engine.stop().await.unwrap();
+
+        // Starting a plain one-shot session afterward must not be treated
+        // as part of the old cycle.
+        let session = engine.start(10, "Ad-hoc", Origin::Human).await.unwrap();
+        assert_eq!(session.kind, SessionKind::Work);
+        assert_eq!(session.cycle_index, 0);
+        assert!(engine.inner.lock().await.cycle.is_none());
+    }
 }
@@ -0,0 +1,166 @@
+use async_trait::async_trait;
+use chrono::Utc;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::sync::Arc;
+use tokio::sync::Mutex;
+
+pub type WorkerId = String;
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+pub enum WorkerState {
+    Active,
+    Idle,
+    Dead,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WorkerInfo {
+    pub id: WorkerId,
+    pub state: WorkerState,
+    pub last_error: Option<String>,
+    pub iterations: u64,
+    pub last_active: String,
+}
+
+impl WorkerInfo {
+    fn new(id: WorkerId) -> Self {
+        WorkerInfo {
+            id,
+            state: WorkerState::Idle,
+            last_error: None,
+            iterations: 0,
+            last_active: Utc::now().to_rfc3339(),
+        }
+    }
+}
+
+/// A supervised background task. `step` runs one unit of work (e.g. one
+/// accepted connection, one forwarded event) and is called in a loop by the
+/// `WorkerManager` until it returns `Err`, at which point the worker is
+/// marked `Dead` rather than silently dropped.
+#[async_trait]
+pub trait Worker: Send + 'static {
+    fn id(&self) -> &str;
+    async fn step(&mut self) -> Result<(), String>;
+}
+
+/// Tracks the liveness of background tasks (the socket listener, the event
+/// forwarder, the timer engine) so an agent or the UI can tell whether they
+/// are still running.
+#[derive(Clone, Default)]
+pub struct WorkerManager {
+    workers: Arc<Mutex<HashMap<WorkerId, WorkerInfo>>>,
+}
+
+impl WorkerManager {
+    pub fn new() -> Self {
+        WorkerManager::default()
+    }
+
+    /// Registers and drives `worker` on a spawned task, looping over `step`
+    /// until it errors. The spawned task is detached; nothing is returned to
+    /// await it with.
+    pub fn spawn<W: Worker>(&self, mut worker: W) {
+        let id: WorkerId = worker.id().to_string();
+        let workers = Arc::clone(&self.workers);
+
+        tokio::spawn(async move {
+            {
+                let mut workers = workers.lock().await;
+                workers
+                    .entry(id.clone())
+                    .or_insert_with(|| WorkerInfo::new(id.clone()));
+            }
+
+            loop {
+                {
+                    let mut workers = workers.lock().await;
+                    if let Some(info) = workers.get_mut(&id) {
+                        info.state = WorkerState::Active;
+                    }
+                }
+
+                match worker.step().await {
+                    Ok(()) => {
+                        let mut workers = workers.lock().await;
+                        if let Some(info) = workers.get_mut(&id) {
+                            info.state = WorkerState::Idle;
+                            info.iterations += 1;
+                            info.last_active = Utc::now().to_rfc3339();
+                        }
+                    }
+                    Err(e) => {
+                        let mut workers = workers.lock().await;
+                        if let Some(info) = workers.get_mut(&id) {
+                            info.state = WorkerState::Dead;
+                            info.last_error = Some(e);
+                            info.last_active = Utc::now().to_rfc3339();
+                        }
+                        break;
+                    }
+                }
+            }
+        });
+    }
+
+    pub async fn list(&self) -> Vec<WorkerInfo> {
+        let workers = self.workers.lock().await;
+        let mut infos: Vec<WorkerInfo> = workers.values().cloned().collect();
+        infos.sort_by(|a, b| a.id.cmp(&b.id));
+        infos
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    struct CountingWorker {
+        id: String,
+        calls: Arc<AtomicUsize>,
+        fail_after: usize,
+    }
+
+    #[async_trait]
+    impl Worker for CountingWorker {
+        fn id(&self) -> &str {
+            &self.id
+        }
+
+        async fn step(&mut self) -> Result<(), String> {
+            let n = self.calls.fetch_add(1, Ordering::SeqCst) + 1;
+            if n >= self.fail_after {
+                Err("boom".to_string())
+            } else {
+                Ok(())
+            }
+        }
+    }
+
+    #[tokio::test]
+    async fn test_worker_marked_dead_on_error() {
+        let manager = WorkerManager::new();
+        let calls = Arc::new(AtomicUsize::new(0));
+        manager.spawn(CountingWorker {
+            id: "test-worker".to_string(),
+            calls: Arc::clone(&calls),
+            fail_after: 3,
+        });
+
+        // Give the spawned task time to run its steps and die.
+        tokio::time::sleep(std::time::Duration::from_millis(50)).await;
+
+        let infos = manager.list().await;
+        let info = infos.iter().find(|w| w.id == "test-worker").unwrap();
+        assert_eq!(info.state, WorkerState::Dead);
+        assert_eq!(info.last_error.as_deref(), Some("boom"));
+    }
+
+    #[tokio::test]
+    async fn test_unknown_worker_list_is_empty() {
+        let manager = WorkerManager::new();
+        assert!(manager.list().await.is_empty());
+    }
+}